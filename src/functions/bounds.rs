@@ -0,0 +1,102 @@
+use nalgebra::DVector;
+use argmin::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Box lower <= x <= upper, componentwise.
+/// Infinite bounds are allowed, except for sampling.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Bounds {
+    lower: DVector<f64>,
+    upper: DVector<f64>,
+}
+
+impl Bounds {
+    pub fn new(lower: DVector<f64>, upper: DVector<f64>) -> Result<Self, Error> {
+        if lower.len() != upper.len() {
+            return Err(Error::msg("Invalid bounds: lower and upper bounds have different dimensions."));
+        }
+        if lower.iter().zip(upper.iter()).any(|(l, u)| !(l <= u)) {
+            return Err(Error::msg("Invalid bounds: a lower bound is greater than the upper bound."));
+        }
+
+        Ok(Bounds { lower, upper })
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.lower.len()
+    }
+
+    pub fn lower(&self) -> &DVector<f64> {
+        &self.lower
+    }
+
+    pub fn upper(&self) -> &DVector<f64> {
+        &self.upper
+    }
+
+    /// Whether all the bounds are finite, as sampling requires.
+    pub fn is_finite(&self) -> bool {
+        self.lower.iter().chain(self.upper.iter()).all(|b| b.is_finite())
+    }
+
+    pub fn contains(&self, param: &DVector<f64>) -> bool {
+        (0 .. self.dimension()).all(|i| self.lower[i] <= param[i] && param[i] <= self.upper[i])
+    }
+
+    /// Closest point of the box.
+    pub fn project(&self, param: &DVector<f64>) -> DVector<f64> {
+        DVector::from_fn(self.dimension(), |i, _| param[i].max(self.lower[i]).min(self.upper[i]))
+    }
+
+    /// Indices of the variables at a bound, where the gradient
+    /// points outside the box.
+    pub fn active_set(&self, param: &DVector<f64>, gradient: &DVector<f64>) -> Vec<usize> {
+        (0 .. self.dimension())
+            .filter(|&i| {
+                (param[i] <= self.lower[i] && gradient[i] > 0.0)
+                    || (param[i] >= self.upper[i] && gradient[i] < 0.0)
+            })
+            .collect()
+    }
+
+    /// P(x - ∇f(x)) - x, which vanishes exactly at the stationary points
+    /// of the problem restricted to the box.
+    pub fn projected_gradient(&self, param: &DVector<f64>, gradient: &DVector<f64>) -> DVector<f64> {
+        self.project(&(param - gradient)) - param
+    }
+
+    /// Uniformly distributed point of the box.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> DVector<f64> {
+        DVector::from_fn(self.dimension(), |i, _| {
+            self.lower[i] + rng.gen::<f64>() * (self.upper[i] - self.lower[i])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let bounds = Bounds::new(
+            DVector::from_row_slice(&[-1.0, 0.0]),
+            DVector::from_row_slice(&[1.0, f64::INFINITY]),
+        ).unwrap();
+
+        assert!(!bounds.is_finite());
+
+        let param = DVector::from_row_slice(&[-2.0, 3.0]);
+        assert!(!bounds.contains(&param));
+        assert_eq!(DVector::from_row_slice(&[-1.0, 3.0]), bounds.project(&param));
+
+        let param = DVector::from_row_slice(&[-1.0, 0.5]);
+        let gradient = DVector::from_row_slice(&[2.0, 1.0]);
+        assert_eq!(vec![0], bounds.active_set(&param, &gradient));
+        assert_eq!(DVector::from_row_slice(&[0.0, -0.5]), bounds.projected_gradient(&param, &gradient));
+
+        assert!(Bounds::new(DVector::from_element(2, 1.0), DVector::from_element(2, 0.0)).is_err());
+        assert!(Bounds::new(DVector::from_element(2, 0.0), DVector::from_element(3, 1.0)).is_err());
+    }
+}
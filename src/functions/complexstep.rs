@@ -0,0 +1,207 @@
+use nalgebra::{Complex, ComplexField, DMatrix, DVector};
+use argmin::prelude::*;
+
+use crate::functions::Function;
+use crate::functions::finitediff::step_size;
+
+/// Imaginary step, small enough for the truncation error to vanish:
+/// there is no subtractive cancellation to worry about.
+static STEP : f64 = 1E-20;
+
+/// Real-analytic objective, written once for any real or complex scalar.
+pub trait Analytic : Clone {
+    fn evaluate<N>(&self, param: &DVector<N>) -> N
+    where
+        N: ComplexField<RealField = f64>;
+
+    /// Analytic gradient, if available. It's used for computing
+    /// the Hessian columns by complex-step differentiation.
+    fn gradient<N>(&self, _param: &DVector<N>) -> Option<DVector<N>>
+    where
+        N: ComplexField<RealField = f64>
+    {
+        None
+    }
+}
+
+/// Complex-step derivatives of an analytic function,
+/// ∂f/∂x_j = Im f(x + i h e_j) / h.
+///
+/// If the function provides its gradient, the Hessian columns are computed
+/// with complex steps of the gradient, otherwise with central differences
+/// of the complex-step gradient.
+///
+/// Reference:
+///
+/// Joaquim R. R. A. Martins, Peter Sturdza and Juan J. Alonso (2003).
+/// The Complex-Step Derivative Approximation.
+#[derive(Clone)]
+pub struct ComplexStep<A>
+where
+    A: Analytic
+{
+    func: A,
+}
+
+impl<A> ComplexStep<A>
+where
+    A: Analytic
+{
+    pub fn new(func: A) -> Self {
+        ComplexStep { func }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.func
+    }
+
+    pub fn gradient_at(&self, param: &DVector<f64>) -> DVector<f64> {
+        let complex = to_complex(param);
+
+        DVector::from_fn(param.len(), |j, _| {
+            let mut shifted = complex.clone();
+            shifted[j].im = STEP;
+            self.func.evaluate(&shifted).imaginary() / STEP
+        })
+    }
+
+    pub fn hessian_at(&self, param: &DVector<f64>) -> DMatrix<f64> {
+        let n = param.len();
+        let complex = to_complex(param);
+        let mut hessian = DMatrix::zeros(n, n);
+
+        for j in 0 .. n {
+            let mut shifted = complex.clone();
+            shifted[j].im = STEP;
+
+            let column = match self.func.gradient(&shifted) {
+                Some(grad) => grad.map(|g| g.imaginary() / STEP),
+                None => {
+                    let h = step_size(f64::EPSILON.cbrt(), param[j]);
+
+                    let mut forward = param.clone();
+                    forward[j] += h;
+                    let mut backward = param.clone();
+                    backward[j] -= h;
+
+                    (self.gradient_at(&forward) - self.gradient_at(&backward)) / (2.0 * h)
+                }
+            };
+            hessian.set_column(j, &column);
+        }
+
+        0.5 * (&hessian + hessian.transpose())
+    }
+}
+
+fn to_complex(param: &DVector<f64>) -> DVector<Complex<f64>> {
+    param.map(|x| Complex::new(x, 0.0))
+}
+
+impl<A> ArgminOp for ComplexStep<A>
+where
+    A: Analytic
+{
+    type Param = DVector<f64>;
+    type Output = f64;
+    type Hessian = DMatrix<f64>;
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self.func.evaluate(param))
+    }
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        Ok(self.gradient_at(param))
+    }
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        Ok(self.hessian_at(param))
+    }
+}
+
+impl<A> Function for ComplexStep<A>
+where
+    A: Analytic
+{}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::rosenbrock::Rosenbrock2D;
+    use crate::solvers::newton::NewtonWithModifications;
+
+    #[derive(Clone)]
+    struct Rosenbrock {}
+
+    impl Analytic for Rosenbrock {
+        fn evaluate<N>(&self, param: &DVector<N>) -> N
+        where
+            N: ComplexField<RealField = f64>
+        {
+            let b = N::from_real(100.0);
+            let (x1, x2) = (param[0], param[1]);
+
+            // Plain products, polar forms of complex powers lose the imaginary step.
+            let a = N::from_real(1.0) - x1;
+            let c = x2 - x1 * x1;
+            a * a + b * c * c
+        }
+    }
+
+    /// Same function, providing the analytic gradient too.
+    #[derive(Clone)]
+    struct RosenbrockWithGradient {}
+
+    impl Analytic for RosenbrockWithGradient {
+        fn evaluate<N>(&self, param: &DVector<N>) -> N
+        where
+            N: ComplexField<RealField = f64>
+        {
+            Rosenbrock {}.evaluate(param)
+        }
+
+        fn gradient<N>(&self, param: &DVector<N>) -> Option<DVector<N>>
+        where
+            N: ComplexField<RealField = f64>
+        {
+            let b = N::from_real(100.0);
+            let (x1, x2) = (param[0], param[1]);
+
+            let c = x2 - x1 * x1;
+            Some(DVector::from_row_slice(&[
+                N::from_real(-2.0) * (N::from_real(1.0) - x1) - N::from_real(4.0) * b * x1 * c,
+                N::from_real(2.0) * b * c
+            ]))
+        }
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let expected = Rosenbrock2D::new(1.0, 100.0);
+        let param = DVector::from_row_slice(&[-1.2, 1.0]);
+
+        let grad = expected.gradient(&param).unwrap();
+        let hessian = expected.hessian(&param).unwrap();
+
+        // machine precision
+        let func = ComplexStep::new(RosenbrockWithGradient {});
+        assert!((func.gradient_at(&param) - &grad).norm() / grad.norm() < 1E-14);
+        assert!((func.hessian_at(&param) - &hessian).norm() / hessian.norm() < 1E-14);
+
+        let func = ComplexStep::new(Rosenbrock {});
+        assert!((func.gradient_at(&param) - &grad).norm() / grad.norm() < 1E-14);
+        assert!((func.hessian_at(&param) - &hessian).norm() / hessian.norm() < 1E-8);
+    }
+
+    #[test]
+    fn test_newton() {
+        let func = ComplexStep::new(Rosenbrock {});
+        let x0 = DVector::from_row_slice(&[-1.2, 1.0]);
+
+        let res = func.solve(NewtonWithModifications::new(), x0);
+
+        assert!((&res.state.param - DVector::from_row_slice(&[1.0, 1.0])).norm() < 1E-4);
+    }
+}
@@ -0,0 +1,382 @@
+use nalgebra::{DMatrix, DVector};
+use argmin::prelude::*;
+
+use crate::functions::Function;
+use crate::functions::bounds::Bounds;
+
+/// Linear constraints A x = b, or A x <= b, depending on their use.
+#[derive(Clone, Debug)]
+pub struct LinearConstraints {
+    mat: DMatrix<f64>,
+    rhs: DVector<f64>,
+}
+
+impl LinearConstraints {
+    pub fn new(mat: DMatrix<f64>, rhs: DVector<f64>) -> Result<Self, Error> {
+        if mat.nrows() != rhs.len() {
+            return Err(Error::msg("Invalid linear constraints: the matrix and the right hand side have different numbers of rows."));
+        }
+
+        Ok(LinearConstraints { mat, rhs })
+    }
+
+    pub fn mat(&self) -> &DMatrix<f64> {
+        &self.mat
+    }
+
+    pub fn rhs(&self) -> &DVector<f64> {
+        &self.rhs
+    }
+
+    pub fn len(&self) -> usize {
+        self.rhs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rhs.is_empty()
+    }
+
+    /// A x - b
+    pub fn residual(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        if self.mat.ncols() != param.len() {
+            return Err(Error::msg("The linear constraints don't match the dimension of the parameter."));
+        }
+
+        Ok(&self.mat * param - &self.rhs)
+    }
+}
+
+/// Nonlinear constraints c_E(x) = 0 and c_I(x) <= 0, with their Jacobians.
+/// There are none of either kind by default.
+pub trait Constraints : Clone {
+    fn equality(&self, _param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        Ok(DVector::zeros(0))
+    }
+
+    fn equality_jacobian(&self, param: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+        Ok(DMatrix::zeros(0, param.len()))
+    }
+
+    fn inequality(&self, _param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        Ok(DVector::zeros(0))
+    }
+
+    fn inequality_jacobian(&self, param: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+        Ok(DMatrix::zeros(0, param.len()))
+    }
+}
+
+/// No nonlinear constraints.
+impl Constraints for () {}
+
+/// Stack the rows of matrices with the same number of columns.
+fn stack(blocks: &[DMatrix<f64>], ncols: usize) -> DMatrix<f64> {
+    let nrows = blocks.iter().map(|block| block.nrows()).sum();
+    let mut mat = DMatrix::zeros(nrows, ncols);
+
+    let mut row = 0;
+    for block in blocks {
+        mat.rows_mut(row, block.nrows()).copy_from(block);
+        row += block.nrows();
+    }
+    mat
+}
+
+/// Stack vectors.
+fn concat(blocks: &[DVector<f64>]) -> DVector<f64> {
+    DVector::from_iterator(
+        blocks.iter().map(|block| block.len()).sum(),
+        blocks.iter().flat_map(|block| block.iter().cloned())
+    )
+}
+
+/// Minimize f(x) subject to
+///
+///  A_E x = b_E,       c_E(x) = 0,
+///  A_I x <= b_I,      c_I(x) <= 0,
+///  l <= x <= u.
+///
+/// All the constraints are collected into the equalities
+/// [A_E x - b_E; c_E(x)] = 0 and the inequalities
+/// [A_I x - b_I; c_I(x); l - x; x - u] <= 0,
+/// where only the finite bounds appear.
+/// The Lagrangian is L(x, λ_E, λ_I) = f(x) + λ_Eᵀ c_E(x) + λ_Iᵀ c_I(x),
+/// with λ_I >= 0 at a solution.
+#[derive(Clone)]
+pub struct ConstrainedProblem<O, C = ()>
+where
+    O: Function,
+    C: Constraints
+{
+    func: O,
+    bounds: Option<Bounds>,
+    linear_equalities: Option<LinearConstraints>,
+    linear_inequalities: Option<LinearConstraints>,
+    nonlinear: C,
+}
+
+impl<O> ConstrainedProblem<O, ()>
+where
+    O: Function
+{
+    pub fn new(func: O) -> Self {
+        ConstrainedProblem {
+            func,
+            bounds: None,
+            linear_equalities: None,
+            linear_inequalities: None,
+            nonlinear: (),
+        }
+    }
+
+    /// All the constraints as A_E x = d_E and A_I x <= d_I,
+    /// in the order of equality and inequality.
+    pub fn linear_constraints(&self, dimension: usize) -> Result<(LinearConstraints, LinearConstraints), Error> {
+        // c(x) = A x - d, with c(0) = -d
+        let origin = DVector::zeros(dimension);
+
+        Ok((
+            LinearConstraints::new(self.equality_jacobian(&origin)?, -self.equality(&origin)?)?,
+            LinearConstraints::new(self.inequality_jacobian(&origin)?, -self.inequality(&origin)?)?,
+        ))
+    }
+}
+
+impl<O, C> ConstrainedProblem<O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    pub fn with_linear_equalities(mut self, constraints: LinearConstraints) -> Self {
+        self.linear_equalities = Some(constraints);
+        self
+    }
+
+    pub fn with_linear_inequalities(mut self, constraints: LinearConstraints) -> Self {
+        self.linear_inequalities = Some(constraints);
+        self
+    }
+
+    pub fn with_nonlinear<D: Constraints>(self, nonlinear: D) -> ConstrainedProblem<O, D> {
+        ConstrainedProblem {
+            func: self.func,
+            bounds: self.bounds,
+            linear_equalities: self.linear_equalities,
+            linear_inequalities: self.linear_inequalities,
+            nonlinear,
+        }
+    }
+
+    pub fn function(&self) -> &O {
+        &self.func
+    }
+
+    pub fn bounds(&self) -> Option<&Bounds> {
+        self.bounds.as_ref()
+    }
+
+    pub fn linear_equalities(&self) -> Option<&LinearConstraints> {
+        self.linear_equalities.as_ref()
+    }
+
+    pub fn linear_inequalities(&self) -> Option<&LinearConstraints> {
+        self.linear_inequalities.as_ref()
+    }
+
+    pub fn nonlinear(&self) -> &C {
+        &self.nonlinear
+    }
+
+    /// Indices of the finite lower and upper bounds.
+    fn finite_bounds(&self) -> (Vec<usize>, Vec<usize>) {
+        match &self.bounds {
+            Some(bounds) => (
+                (0 .. bounds.dimension()).filter(|&i| bounds.lower()[i].is_finite()).collect(),
+                (0 .. bounds.dimension()).filter(|&i| bounds.upper()[i].is_finite()).collect(),
+            ),
+            None => (Vec::new(), Vec::new()),
+        }
+    }
+
+    /// All the equality constraints, [A_E x - b_E; c_E(x)].
+    pub fn equality(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        let linear = match &self.linear_equalities {
+            Some(constraints) => constraints.residual(param)?,
+            None => DVector::zeros(0),
+        };
+
+        Ok(concat(&[linear, self.nonlinear.equality(param)?]))
+    }
+
+    pub fn equality_jacobian(&self, param: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+        let n = param.len();
+        let linear = match &self.linear_equalities {
+            Some(constraints) => constraints.mat().clone(),
+            None => DMatrix::zeros(0, n),
+        };
+
+        Ok(stack(&[linear, self.nonlinear.equality_jacobian(param)?], n))
+    }
+
+    /// All the inequality constraints, [A_I x - b_I; c_I(x); l - x; x - u].
+    pub fn inequality(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        let linear = match &self.linear_inequalities {
+            Some(constraints) => constraints.residual(param)?,
+            None => DVector::zeros(0),
+        };
+
+        let (lower, upper) = self.finite_bounds();
+        let (lower_values, upper_values) = match &self.bounds {
+            Some(bounds) => {
+                if bounds.dimension() != param.len() {
+                    return Err(Error::msg("The bounds don't match the dimension of the parameter."));
+                }
+
+                (
+                    DVector::from_iterator(lower.len(), lower.iter().map(|&i| bounds.lower()[i] - param[i])),
+                    DVector::from_iterator(upper.len(), upper.iter().map(|&i| param[i] - bounds.upper()[i])),
+                )
+            },
+            None => (DVector::zeros(0), DVector::zeros(0)),
+        };
+
+        Ok(concat(&[linear, self.nonlinear.inequality(param)?, lower_values, upper_values]))
+    }
+
+    pub fn inequality_jacobian(&self, param: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+        let n = param.len();
+        let linear = match &self.linear_inequalities {
+            Some(constraints) => constraints.mat().clone(),
+            None => DMatrix::zeros(0, n),
+        };
+
+        let (lower, upper) = self.finite_bounds();
+        let mut lower_jacobian = DMatrix::zeros(lower.len(), n);
+        for (row, &i) in lower.iter().enumerate() {
+            lower_jacobian[(row, i)] = -1.0;
+        }
+        let mut upper_jacobian = DMatrix::zeros(upper.len(), n);
+        for (row, &i) in upper.iter().enumerate() {
+            upper_jacobian[(row, i)] = 1.0;
+        }
+
+        Ok(stack(&[linear, self.nonlinear.inequality_jacobian(param)?, lower_jacobian, upper_jacobian], n))
+    }
+
+    /// ℓ1 norm of the constraint violation, Σ |c_E(x)| + Σ max(c_I(x), 0).
+    pub fn violation(&self, param: &DVector<f64>) -> Result<f64, Error> {
+        let equality = self.equality(param)?;
+        let inequality = self.inequality(param)?;
+
+        Ok(equality.iter().map(|c| c.abs()).sum::<f64>()
+            + inequality.iter().map(|c| c.max(0.0)).sum::<f64>())
+    }
+
+    /// Whether every constraint is violated by at most tolerance.
+    pub fn is_feasible(&self, param: &DVector<f64>, tolerance: f64) -> Result<bool, Error> {
+        let equality = self.equality(param)?;
+        let inequality = self.inequality(param)?;
+
+        Ok(equality.iter().all(|c| c.abs() <= tolerance)
+            && inequality.iter().all(|&c| c <= tolerance))
+    }
+
+    fn check_multipliers(&self, equality: &DVector<f64>, inequality: &DVector<f64>, param: &DVector<f64>)
+        -> Result<(), Error> {
+        if equality.len() != self.equality(param)?.len() || inequality.len() != self.inequality(param)?.len() {
+            return Err(Error::msg("The multipliers don't match the number of constraints."));
+        }
+        Ok(())
+    }
+
+    /// L(x, λ_E, λ_I) = f(x) + λ_Eᵀ c_E(x) + λ_Iᵀ c_I(x)
+    pub fn lagrangian(&self, param: &DVector<f64>, lambda_e: &DVector<f64>, lambda_i: &DVector<f64>)
+        -> Result<f64, Error> {
+        self.check_multipliers(lambda_e, lambda_i, param)?;
+
+        Ok(self.func.apply(param)?
+            + lambda_e.dot(&self.equality(param)?)
+            + lambda_i.dot(&self.inequality(param)?))
+    }
+
+    /// ∇f(x) + J_E(x)ᵀ λ_E + J_I(x)ᵀ λ_I
+    pub fn lagrangian_gradient(&self, param: &DVector<f64>, lambda_e: &DVector<f64>, lambda_i: &DVector<f64>)
+        -> Result<DVector<f64>, Error> {
+        self.check_multipliers(lambda_e, lambda_i, param)?;
+
+        Ok(self.func.gradient(param)?
+            + self.equality_jacobian(param)?.transpose() * lambda_e
+            + self.inequality_jacobian(param)?.transpose() * lambda_i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::fixtures::Disk;
+    use crate::functions::rosenbrock::Rosenbrock2D;
+
+    fn problem() -> ConstrainedProblem<Rosenbrock2D, Disk> {
+        let bounds = Bounds::new(
+            DVector::from_row_slice(&[0.0, f64::NEG_INFINITY]),
+            DVector::from_row_slice(&[f64::INFINITY, 0.5]),
+        ).unwrap();
+
+        // x1 - x2 = 0
+        let equalities = LinearConstraints::new(
+            DMatrix::from_row_slice(1, 2, &[1.0, -1.0]),
+            DVector::from_element(1, 0.0),
+        ).unwrap();
+
+        ConstrainedProblem::new(Rosenbrock2D::new(1.0, 100.0))
+            .with_bounds(bounds)
+            .with_linear_equalities(equalities)
+            .with_nonlinear(Disk {})
+    }
+
+    #[test]
+    fn test_constraints() {
+        let problem = problem();
+        let param = DVector::from_row_slice(&[1.0, 1.0]);
+
+        assert_eq!(DVector::from_element(1, 0.0), problem.equality(&param).unwrap());
+        // disk, lower bound on x1, upper bound on x2
+        assert_eq!(DVector::from_row_slice(&[1.0, -1.0, 0.5]), problem.inequality(&param).unwrap());
+        assert_eq!(
+            DMatrix::from_row_slice(3, 2, &[2.0, 2.0, -1.0, 0.0, 0.0, 1.0]),
+            problem.inequality_jacobian(&param).unwrap()
+        );
+
+        assert!((problem.violation(&param).unwrap() - 1.5).abs() < 1E-15);
+        assert!(!problem.is_feasible(&param, 1E-8).unwrap());
+        assert!(problem.is_feasible(&DVector::from_row_slice(&[0.5, 0.5]), 1E-8).unwrap());
+    }
+
+    #[test]
+    fn test_lagrangian() {
+        let problem = problem();
+        let param = DVector::from_row_slice(&[0.3, 0.2]);
+        let lambda_e = DVector::from_element(1, 0.5);
+        let lambda_i = DVector::from_row_slice(&[1.0, 2.0, 3.0]);
+
+        let gradient = problem.lagrangian_gradient(&param, &lambda_e, &lambda_i).unwrap();
+        for j in 0 .. 2 {
+            let h = 1E-6;
+            let mut forward = param.clone();
+            forward[j] += h;
+            let mut backward = param.clone();
+            backward[j] -= h;
+
+            let difference = (problem.lagrangian(&forward, &lambda_e, &lambda_i).unwrap()
+                - problem.lagrangian(&backward, &lambda_e, &lambda_i).unwrap()) / (2.0 * h);
+            assert!((difference - gradient[j]).abs() < 1E-6);
+        }
+
+        assert!(problem.lagrangian(&param, &lambda_e, &DVector::zeros(2)).is_err());
+    }
+}
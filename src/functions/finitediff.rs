@@ -0,0 +1,206 @@
+use nalgebra::{DMatrix, DVector};
+use argmin::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::functions::Function;
+
+/// Finite difference schemes for the gradient.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Scheme {
+    /// (f(x + h) - f(x)) / h, first order.
+    Forward,
+    /// (f(x + h) - f(x - h)) / 2h, second order.
+    Central,
+    /// Richardson extrapolation of central differences with steps h and h/2,
+    /// fourth order.
+    Richardson,
+}
+
+impl Scheme {
+    /// Relative step size, balancing truncation and rounding errors.
+    fn relative_step(&self) -> f64 {
+        match *self {
+            Scheme::Forward => f64::EPSILON.sqrt(),
+            Scheme::Central => f64::EPSILON.cbrt(),
+            Scheme::Richardson => f64::EPSILON.powf(0.2),
+        }
+    }
+
+    /// Relative step size for differencing the gradient, when building the Hessian.
+    fn relative_hessian_step(&self) -> f64 {
+        match *self {
+            Scheme::Forward => f64::EPSILON.cbrt(),
+            Scheme::Central | Scheme::Richardson => f64::EPSILON.powf(0.25),
+        }
+    }
+}
+
+/// Step size for the coordinate x, scaled with its magnitude.
+/// The step is rounded so that x + h - x == h holds exactly.
+pub fn step_size(relative_step: f64, x: f64) -> f64 {
+    let h = relative_step * x.abs().max(1.0);
+    (x + h) - x
+}
+
+/// Wrapper providing finite difference gradients and Hessians
+/// for an operator which only implements `apply`.
+/// The Hessian is computed by differencing the gradient,
+/// and symmetrized.
+///
+/// Reference:
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// [chapter 8.1]
+#[derive(Clone)]
+pub struct FiniteDiff<O>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    op: O,
+    scheme: Scheme,
+}
+
+impl<O> FiniteDiff<O>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    pub fn new(op: O, scheme: Scheme) -> Self {
+        FiniteDiff {
+            op,
+            scheme
+        }
+    }
+
+    pub fn inner(&self) -> &O {
+        &self.op
+    }
+
+    pub fn gradient_at(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        let relative_step = self.scheme.relative_step();
+        let value = match self.scheme {
+            Scheme::Forward => self.op.apply(param)?,
+            _ => 0.0,
+        };
+
+        let mut grad = DVector::zeros(param.len());
+        for j in 0 .. param.len() {
+            let h = step_size(relative_step, param[j]);
+
+            grad[j] = match self.scheme {
+                Scheme::Forward => (self.shifted_value(param, j, h)? - value) / h,
+                Scheme::Central => self.central_difference(param, j, h)?,
+                Scheme::Richardson => {
+                    let coarse = self.central_difference(param, j, h)?;
+                    let fine = self.central_difference(param, j, 0.5 * h)?;
+                    (4.0 * fine - coarse) / 3.0
+                },
+            };
+        }
+
+        Ok(grad)
+    }
+
+    pub fn hessian_at(&self, param: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+        let relative_step = self.scheme.relative_hessian_step();
+        let grad = match self.scheme {
+            Scheme::Forward => self.gradient_at(param)?,
+            _ => DVector::zeros(0),
+        };
+
+        let n = param.len();
+        let mut hessian = DMatrix::zeros(n, n);
+        for j in 0 .. n {
+            let h = step_size(relative_step, param[j]);
+
+            let mut forward = param.clone();
+            forward[j] += h;
+
+            let column = match self.scheme {
+                Scheme::Forward => (self.gradient_at(&forward)? - &grad) / h,
+                _ => {
+                    let mut backward = param.clone();
+                    backward[j] -= h;
+                    (self.gradient_at(&forward)? - self.gradient_at(&backward)?) / (2.0 * h)
+                },
+            };
+            hessian.set_column(j, &column);
+        }
+
+        Ok(0.5 * (&hessian + hessian.transpose()))
+    }
+
+    fn shifted_value(&self, param: &DVector<f64>, j: usize, h: f64) -> Result<f64, Error> {
+        let mut shifted = param.clone();
+        shifted[j] += h;
+        self.op.apply(&shifted)
+    }
+
+    fn central_difference(&self, param: &DVector<f64>, j: usize, h: f64) -> Result<f64, Error> {
+        let forward = self.shifted_value(param, j, h)?;
+        let backward = self.shifted_value(param, j, -h)?;
+        Ok((forward - backward) / (2.0 * h))
+    }
+}
+
+impl<O> ArgminOp for FiniteDiff<O>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    type Param = DVector<f64>;
+    type Output = f64;
+    type Hessian = DMatrix<f64>;
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.op.apply(param)
+    }
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        self.gradient_at(param)
+    }
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.hessian_at(param)
+    }
+}
+
+impl<O> Function for FiniteDiff<O>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::rosenbrock::Rosenbrock2D;
+    use crate::solvers::newton::Newton;
+
+    #[test]
+    fn test_derivatives() {
+        let func = Rosenbrock2D::new(1.0, 100.0);
+        let param = DVector::from_row_slice(&[-1.2, 1.0]);
+
+        let grad = func.gradient(&param).unwrap();
+        let hessian = func.hessian(&param).unwrap();
+
+        for &scheme in [Scheme::Forward, Scheme::Central, Scheme::Richardson].iter() {
+            let finitediff = FiniteDiff::new(func.clone(), scheme);
+
+            let grad_error = (finitediff.gradient(&param).unwrap() - &grad).norm();
+            assert!(grad_error / grad.norm() < 1E-6);
+
+            let hessian_error = (finitediff.hessian(&param).unwrap() - &hessian).norm();
+            assert!(hessian_error / hessian.norm() < 1E-3);
+        }
+    }
+
+    #[test]
+    fn test_newton() {
+        let func = FiniteDiff::new(Rosenbrock2D::new(1.0, 100.0), Scheme::Central);
+        let x0 = DVector::from_row_slice(&[-1.2, 1.0]);
+
+        let res = func.solve(Newton::new(), x0);
+        assert!((&res.state.param - DVector::from_row_slice(&[1.0, 1.0])).norm() < 1E-5);
+    }
+}
@@ -0,0 +1,34 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+
+use crate::functions::constraints::Constraints;
+use crate::functions::quadratic::Quadratic;
+
+/// (x1 - 2)² + (x2 - 1)²
+pub fn distance() -> Quadratic {
+    Quadratic::new(
+        DMatrix::from_diagonal_element(2, 2, 2.0),
+        DVector::from_row_slice(&[-4.0, -2.0]),
+        5.0,
+    )
+}
+
+/// x1² + x2² <= 1
+#[derive(Clone)]
+pub struct Disk {}
+
+impl Constraints for Disk {
+    fn inequality(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        Ok(DVector::from_element(1, param.norm_squared() - 1.0))
+    }
+
+    fn inequality_jacobian(&self, param: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+        Ok(DMatrix::from_fn(1, param.len(), |_, j| 2.0 * param[j]))
+    }
+}
+
+/// Minimizer of the distance on the disk, the closest point (2, 1) / √5,
+/// and its multiplier √5 - 1.
+pub fn closest_point() -> (DVector<f64>, f64) {
+    (DVector::from_row_slice(&[2.0, 1.0]) / 5f64.sqrt(), 5f64.sqrt() - 1.0)
+}
@@ -0,0 +1,183 @@
+use nalgebra::{DMatrix, DVector};
+use argmin::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::functions::Function;
+
+/// Least-squares problem, described by its residuals r(x) and their
+/// Jacobian J(x).
+pub trait LeastSquares : Clone {
+    fn residuals(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error>;
+
+    fn jacobian(&self, param: &DVector<f64>) -> Result<DMatrix<f64>, Error>;
+}
+
+/// Loss function ρ applied to every residual.
+/// The parameter of the robust losses is the scale at which a residual
+/// starts being considered an outlier.
+///
+/// Reference:
+///
+/// Bill Triggs et al. (2000). Bundle Adjustment — A Modern Synthesis.
+/// [section 3.4]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Loss {
+    /// Plain least squares, ρ(r) = r² / 2.
+    Linear,
+    Huber(f64),
+    Cauchy(f64),
+    SoftL1(f64),
+    /// Tukey's biweight, residuals beyond the scale are ignored.
+    Tukey(f64),
+}
+
+impl Loss {
+    /// Value ρ(r) of the loss function.
+    pub fn rho(&self, r: f64) -> f64 {
+        match *self {
+            Loss::Linear => 0.5 * r.powi(2),
+            Loss::Huber(k) => {
+                if r.abs() <= k {
+                    0.5 * r.powi(2)
+                }
+                else {
+                    k * (r.abs() - 0.5 * k)
+                }
+            },
+            Loss::Cauchy(k) => 0.5 * k.powi(2) * (1.0 + (r / k).powi(2)).ln(),
+            Loss::SoftL1(k) => k.powi(2) * ((1.0 + (r / k).powi(2)).sqrt() - 1.0),
+            Loss::Tukey(k) => {
+                if r.abs() <= k {
+                    k.powi(2) / 6.0 * (1.0 - (1.0 - (r / k).powi(2)).powi(3))
+                }
+                else {
+                    k.powi(2) / 6.0
+                }
+            },
+        }
+    }
+
+    /// IRLS weight w(r) = ρ'(r) / r.
+    pub fn weight(&self, r: f64) -> f64 {
+        match *self {
+            Loss::Linear => 1.0,
+            Loss::Huber(k) => {
+                if r.abs() <= k { 1.0 } else { k / r.abs() }
+            },
+            Loss::Cauchy(k) => 1.0 / (1.0 + (r / k).powi(2)),
+            Loss::SoftL1(k) => 1.0 / (1.0 + (r / k).powi(2)).sqrt(),
+            Loss::Tukey(k) => {
+                if r.abs() <= k { (1.0 - (r / k).powi(2)).powi(2) } else { 0.0 }
+            },
+        }
+    }
+}
+
+/// Robust least-squares cost Σ ρ(r_i(x)).
+///
+/// The Hessian is the Gauss-Newton approximation Jᵀ W J, where W holds
+/// the IRLS weights of the current residuals. A Newton step on this
+/// function is therefore an iteratively reweighted least-squares step.
+#[derive(Clone)]
+pub struct RobustLeastSquares<P>
+where
+    P: LeastSquares
+{
+    problem: P,
+    loss: Loss,
+}
+
+impl<P> RobustLeastSquares<P>
+where
+    P: LeastSquares
+{
+    pub fn new(problem: P, loss: Loss) -> Self {
+        RobustLeastSquares {
+            problem,
+            loss
+        }
+    }
+
+    pub fn problem(&self) -> &P {
+        &self.problem
+    }
+
+    pub fn loss(&self) -> Loss {
+        self.loss
+    }
+
+    /// Per-residual weights at the given parameter, e.g. at the solution
+    /// for detecting which measurements have been treated as outliers.
+    pub fn weights(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        let residuals = self.problem.residuals(param)?;
+        Ok(residuals.map(|r| self.loss.weight(r)))
+    }
+}
+
+impl<P> ArgminOp for RobustLeastSquares<P>
+where
+    P: LeastSquares
+{
+    type Param = DVector<f64>;
+    type Output = f64;
+    type Hessian = DMatrix<f64>;
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let residuals = self.problem.residuals(param)?;
+        Ok(residuals.iter().map(|&r| self.loss.rho(r)).sum())
+    }
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        let residuals = self.problem.residuals(param)?;
+        let jacobian = self.problem.jacobian(param)?;
+
+        let weighted = residuals.map(|r| self.loss.weight(r) * r);
+        Ok(jacobian.transpose() * weighted)
+    }
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let residuals = self.problem.residuals(param)?;
+        let jacobian = self.problem.jacobian(param)?;
+
+        let weights = residuals.map(|r| self.loss.weight(r));
+        let mut weighted_jacobian = jacobian.clone();
+        for (i, w) in weights.iter().enumerate() {
+            weighted_jacobian.row_mut(i).scale_mut(*w);
+        }
+
+        Ok(jacobian.transpose() * weighted_jacobian)
+    }
+}
+
+impl<P> Function for RobustLeastSquares<P>
+where
+    P: LeastSquares
+{}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights() {
+        let losses = [
+            Loss::Linear,
+            Loss::Huber(1.5),
+            Loss::Cauchy(1.5),
+            Loss::SoftL1(1.5),
+            Loss::Tukey(1.5),
+        ];
+
+        // the weight has to match ρ'(r) / r
+        for loss in losses.iter() {
+            for &r in [-3.0, -0.7, 0.2, 1.0, 2.5].iter() {
+                let h = 1E-6;
+                let derivative = (loss.rho(r + h) - loss.rho(r - h)) / (2.0 * h);
+
+                assert!((derivative / r - loss.weight(r)).abs() < 1E-6);
+            }
+        }
+    }
+}
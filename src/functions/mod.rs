@@ -1,8 +1,18 @@
 use nalgebra::{DMatrix, DVector};
 use argmin::prelude::*;
 
+pub mod bounds;
+pub mod complexstep;
+pub mod constraints;
+pub mod finitediff;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod leastsquares;
+pub mod powell;
 pub mod quadratic;
 pub mod rosenbrock;
+pub mod sparsehessian;
+pub mod system;
 
 pub trait Function : ArgminOp<
     Param = DVector<f64>,
@@ -23,4 +33,27 @@ pub trait Function : ArgminOp<
         .run()
         .unwrap()
     }
+
+    /// Like solve, but without logging, with at most max_iters iterations,
+    /// and returning the errors of the solver.
+    /// The solver may have stopped at max_iters: check converged.
+    fn minimize<S>(self, solver: S, param: Self::Param, max_iters: u64) -> Result<ArgminResult<Self>, Error>
+    where
+        S: Solver<Self>
+    {
+        Executor::new(self, solver, param)
+        .max_iters(max_iters)
+        .run()
+    }
+}
+
+/// Whether the solver stopped because it met its convergence criterion,
+/// rather than at the maximum number of iterations or aborted.
+pub fn converged<O: ArgminOp>(res: &ArgminResult<O>) -> bool {
+    match res.state.termination_reason {
+        TerminationReason::TargetPrecisionReached
+        | TerminationReason::TargetToleranceReached
+        | TerminationReason::TargetCostReached => true,
+        _ => false,
+    }
 }
\ No newline at end of file
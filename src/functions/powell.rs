@@ -0,0 +1,55 @@
+use nalgebra::{DMatrix, DVector};
+use argmin::prelude::*;
+
+use super::system::System;
+
+/// Powell's badly scaled function, the nonlinear system
+/// 10⁴ x1 x2 = 1
+/// exp(-x1) + exp(-x2) = 1.0001
+/// usually started at (0, 1). The root is at (1.098...e-5, 9.106...).
+///
+/// Reference:
+///
+/// Jorge J. Moré, Burton S. Garbow and Kenneth E. Hillstrom (1981).
+/// Testing Unconstrained Optimization Software.
+#[derive(Clone)]
+pub struct PowellBadlyScaled {}
+
+impl PowellBadlyScaled {
+    pub fn new() -> Self {
+        PowellBadlyScaled {}
+    }
+}
+
+impl ArgminOp for PowellBadlyScaled {
+    type Param = DVector<f64>;
+    type Output = DVector<f64>;
+    type Hessian = ();
+    type Jacobian = DMatrix<f64>;
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let x1 = param[0];
+        let x2 = param[1];
+
+        Ok(DVector::from_row_slice(&[
+            1E4 * x1 * x2 - 1.0,
+            (-x1).exp() + (-x2).exp() - 1.0001
+        ]))
+    }
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        let x1 = param[0];
+        let x2 = param[1];
+
+        let mat = DMatrix::from_row_slice(
+            2, 2,
+            &[
+                1E4 * x2, 1E4 * x1,
+                -(-x1).exp(), -(-x2).exp()
+            ]);
+        Ok(mat)
+    }
+}
+
+impl System for PowellBadlyScaled {}
@@ -21,6 +21,18 @@ impl Quadratic {
         }
     }
 
+    pub fn mat_q(&self) -> &DMatrix<f64> {
+        &self.mat_q
+    }
+
+    pub fn b(&self) -> &DVector<f64> {
+        &self.b
+    }
+
+    pub fn c(&self) -> f64 {
+        self.c
+    }
+
     pub fn evaluate_at(&self, at: &DVector<f64>) -> f64 {
         // FIXME: try a more efficient implementation using
         // nalgebra
@@ -0,0 +1,332 @@
+use nalgebra::{DMatrix, DVector};
+use argmin::prelude::*;
+
+use crate::functions::Function;
+use crate::functions::finitediff::step_size;
+
+/// Sparsity pattern of a symmetric matrix.
+/// The diagonal is always assumed to be nonzero.
+#[derive(Clone, Debug)]
+pub struct SparsityPattern {
+    // off-diagonal nonzeros of every column
+    neighbours: Vec<Vec<usize>>,
+}
+
+impl SparsityPattern {
+    /// Pattern of an n x n symmetric matrix, given the positions (i, j)
+    /// of its nonzero entries. Only one of (i, j) and (j, i) is needed.
+    pub fn new(n: usize, nonzeros: &[(usize, usize)]) -> Result<Self, Error> {
+        let mut neighbours = vec![Vec::new(); n];
+
+        for &(i, j) in nonzeros {
+            if i >= n || j >= n {
+                return Err(Error::msg("Invalid sparsity pattern: index out of bounds."));
+            }
+            if i != j && !neighbours[i].contains(&j) {
+                neighbours[i].push(j);
+                neighbours[j].push(i);
+            }
+        }
+
+        Ok(SparsityPattern { neighbours })
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    pub fn neighbours(&self, j: usize) -> &[usize] {
+        &self.neighbours[j]
+    }
+
+    /// Rows of the nonzero entries of column j, diagonal included.
+    fn column(&self, j: usize) -> impl Iterator<Item = usize> + '_ {
+        self.neighbours[j]
+            .iter()
+            .cloned()
+            .chain(std::iter::once(j))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Coloring {
+    /// Columns sharing the same color have no nonzero row in common,
+    /// every entry can be read off the compressed Hessian directly.
+    CurtisPowellReid,
+    /// Symmetric coloring where every path on four vertices uses
+    /// at least three colors. It needs fewer colors,
+    /// and every entry can still be recovered directly from either
+    /// its row or its column.
+    Star,
+}
+
+/// Greedy coloring of the columns, in their natural order.
+///
+/// Reference:
+///
+/// Assefaw H. Gebremedhin, Fredrik Manne and Alex Pothen (2005).
+/// What Color Is Your Jacobian? Graph Coloring for Computing Derivatives.
+pub fn color(pattern: &SparsityPattern, coloring: Coloring) -> Vec<usize> {
+    let n = pattern.dimension();
+    let mut colors: Vec<Option<usize>> = vec![None; n];
+    // forbidden[c] == v when color c is forbidden for the vertex v,
+    // so that the array is reused without being cleared.
+    let mut forbidden = vec![n; n];
+
+    for v in 0 .. n {
+
+        for &w in pattern.neighbours(v) {
+            if let Some(c) = colors[w] {
+                forbidden[c] = v;
+            }
+
+            for &x in pattern.neighbours(w) {
+                if x == v {
+                    continue;
+                }
+                let color_x = match colors[x] {
+                    Some(c) => c,
+                    None => continue,
+                };
+
+                match (coloring, colors[w]) {
+                    // distance-2 coloring
+                    (Coloring::CurtisPowellReid, _) => forbidden[color_x] = v,
+                    // v - w - x would be a bicolored path,
+                    // with w still to be colored.
+                    (Coloring::Star, None) => forbidden[color_x] = v,
+                    // v - w - x - y would be a bicolored path.
+                    (Coloring::Star, Some(color_w)) => {
+                        if pattern.neighbours(x).iter().any(|&y| y != w && colors[y] == Some(color_w)) {
+                            forbidden[color_x] = v;
+                        }
+                    },
+                }
+            }
+        }
+
+        // at most n - 1 colors are forbidden, one per other vertex,
+        // so the search stops after as many steps as forbidden colors.
+        let mut c = 0;
+        while forbidden[c] == v {
+            c += 1;
+        }
+        colors[v] = Some(c);
+    }
+
+    colors.into_iter().map(|c| c.unwrap_or(0)).collect()
+}
+
+/// Wrapper estimating the Hessian of a sparse problem by forward differences
+/// of the gradient, with one gradient evaluation per color
+/// instead of one per coordinate.
+#[derive(Clone)]
+pub struct SparseHessian<O>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    op: O,
+    pattern: SparsityPattern,
+    colors: Vec<usize>,
+}
+
+impl<O> SparseHessian<O>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    pub fn new(op: O, pattern: SparsityPattern, coloring: Coloring) -> Self {
+        let colors = color(&pattern, coloring);
+
+        SparseHessian {
+            op,
+            pattern,
+            colors
+        }
+    }
+
+    pub fn inner(&self) -> &O {
+        &self.op
+    }
+
+    /// Number of gradient evaluations needed for one Hessian,
+    /// besides the gradient at the point itself.
+    pub fn num_colors(&self) -> usize {
+        self.colors.iter().max().map_or(0, |c| c + 1)
+    }
+
+    pub fn hessian_at(&self, param: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+        let n = self.pattern.dimension();
+        if param.len() != n {
+            return Err(Error::msg("The sparsity pattern doesn't match the dimension of the parameter."));
+        }
+
+        let grad = self.op.gradient(param)?;
+        let steps = param.map(|x| step_size(f64::EPSILON.sqrt(), x));
+
+        // compressed[(i, c)] = Σ_{color(k) = c} H_ik h_k
+        let mut compressed = DMatrix::zeros(n, self.num_colors());
+        for c in 0 .. self.num_colors() {
+            let mut shifted = param.clone();
+            for k in (0 .. n).filter(|&k| self.colors[k] == c) {
+                shifted[k] += steps[k];
+            }
+
+            compressed.set_column(c, &(self.op.gradient(&shifted)? - &grad));
+        }
+
+        let mut hessian = DMatrix::zeros(n, n);
+        for j in 0 .. n {
+            for i in self.pattern.column(j) {
+                if i > j {
+                    continue;
+                }
+
+                // H_ij can be read in row i, if j is the only column of its color there,
+                // otherwise the coloring guarantees that it can be read in row j.
+                let value = if self.is_unique(i, j) {
+                    compressed[(i, self.colors[j])] / steps[j]
+                }
+                else {
+                    compressed[(j, self.colors[i])] / steps[i]
+                };
+
+                hessian[(i, j)] = value;
+                hessian[(j, i)] = value;
+            }
+        }
+
+        Ok(hessian)
+    }
+
+    /// Whether j is the only nonzero column of row i with its color.
+    fn is_unique(&self, i: usize, j: usize) -> bool {
+        self.pattern
+            .column(i)
+            .filter(|&k| self.colors[k] == self.colors[j])
+            .count() == 1
+    }
+}
+
+impl<O> ArgminOp for SparseHessian<O>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    type Param = DVector<f64>;
+    type Output = f64;
+    type Hessian = DMatrix<f64>;
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.op.apply(param)
+    }
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        self.op.gradient(param)
+    }
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        self.hessian_at(param)
+    }
+}
+
+impl<O> Function for SparseHessian<O>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::newton::NewtonWithModifications;
+
+    /// Chained Rosenbrock function, with a tridiagonal Hessian.
+    #[derive(Clone)]
+    struct Chained {}
+
+    impl ArgminOp for Chained {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = DMatrix<f64>;
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((0 .. param.len() - 1)
+                .map(|i| (1.0 - param[i]).powi(2) + 100.0 * (param[i + 1] - param[i].powi(2)).powi(2))
+                .sum())
+        }
+
+        fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+            let mut grad = DVector::zeros(param.len());
+            for i in 0 .. param.len() - 1 {
+                let c = param[i + 1] - param[i].powi(2);
+                grad[i] += -2.0 * (1.0 - param[i]) - 400.0 * param[i] * c;
+                grad[i + 1] += 200.0 * c;
+            }
+            Ok(grad)
+        }
+
+        fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+            let n = param.len();
+            let mut hessian = DMatrix::zeros(n, n);
+            for i in 0 .. n - 1 {
+                hessian[(i, i)] += 2.0 - 400.0 * param[i + 1] + 1200.0 * param[i].powi(2);
+                hessian[(i, i + 1)] += -400.0 * param[i];
+                hessian[(i + 1, i)] += -400.0 * param[i];
+                hessian[(i + 1, i + 1)] += 200.0;
+            }
+            Ok(hessian)
+        }
+    }
+
+    fn tridiagonal(n: usize) -> SparsityPattern {
+        let nonzeros: Vec<_> = (0 .. n - 1).map(|i| (i, i + 1)).collect();
+        SparsityPattern::new(n, &nonzeros).unwrap()
+    }
+
+    #[test]
+    fn test_arrow_coloring() {
+        let nonzeros: Vec<_> = (1 .. 10).map(|i| (0, i)).collect();
+        let pattern = SparsityPattern::new(10, &nonzeros).unwrap();
+
+        let cpr = color(&pattern, Coloring::CurtisPowellReid);
+        assert_eq!(10, cpr.iter().max().unwrap() + 1);
+
+        let star = color(&pattern, Coloring::Star);
+        assert_eq!(2, star.iter().max().unwrap() + 1);
+    }
+
+    #[test]
+    fn test_large_coloring() {
+        // linear in the number of columns for a fixed bandwidth
+        let pattern = tridiagonal(100000);
+
+        for &coloring in [Coloring::CurtisPowellReid, Coloring::Star].iter() {
+            assert_eq!(3, color(&pattern, coloring).iter().max().unwrap() + 1);
+        }
+    }
+
+    #[test]
+    fn test_hessian() {
+        let param = DVector::from_fn(20, |i, _| 0.1 * i as f64 - 1.0);
+        let expected = Chained {}.hessian(&param).unwrap();
+
+        for &coloring in [Coloring::CurtisPowellReid, Coloring::Star].iter() {
+            let func = SparseHessian::new(Chained {}, tridiagonal(20), coloring);
+            assert_eq!(3, func.num_colors());
+
+            let hessian = func.hessian(&param).unwrap();
+            assert!((hessian - &expected).norm() / expected.norm() < 1E-6);
+        }
+    }
+
+    #[test]
+    fn test_newton() {
+        let func = SparseHessian::new(Chained {}, tridiagonal(10), Coloring::Star);
+        let x0 = DVector::from_element(10, -1.0);
+
+        let res = func.solve(NewtonWithModifications::new(), x0);
+        assert!(res.operator.gradient(&res.state.param).unwrap().norm() < 1E-5);
+    }
+}
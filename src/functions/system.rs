@@ -0,0 +1,69 @@
+use nalgebra::{DMatrix, DVector};
+use argmin::prelude::*;
+
+/// Square system of nonlinear equations F(x) = 0.
+/// `apply` evaluates the residual F(x), `jacobian` its Jacobian.
+pub trait System : ArgminOp<
+    Param = DVector<f64>,
+    Output = DVector<f64>,
+    Hessian = (),
+    Jacobian = DMatrix<f64>,
+    Float = f64
+> {
+    fn solve<S>(self, solver: S, param: Self::Param) -> Result<ArgminResult<Self>, Error>
+    where
+        Self: Clone,
+        S: Solver<Self>
+    {
+        Executor::new(self, solver, param)
+        .add_observer(ArgminSlogLogger::term(), ObserverMode::Always)
+        .max_iters(100)
+        .run()
+    }
+}
+
+/// Merit function ½‖F(x)‖² of a nonlinear system,
+/// used for globalizing the solvers with a line search.
+#[derive(Clone)]
+pub struct Merit<'a, O>
+where
+    O: ArgminOp
+{
+    system: &'a O,
+}
+
+impl<'a, O> Merit<'a, O>
+where
+    O: ArgminOp
+{
+    pub fn new(system: &'a O) -> Self {
+        Merit { system }
+    }
+}
+
+impl<'a, O> ArgminOp for Merit<'a, O>
+where
+    O: ArgminOp<
+        Param = DVector<f64>,
+        Output = DVector<f64>,
+        Jacobian = DMatrix<f64>,
+        Float = f64
+    >
+{
+    type Param = DVector<f64>;
+    type Output = f64;
+    type Hessian = ();
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let residual = self.system.apply(param)?;
+        Ok(0.5 * residual.norm_squared())
+    }
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        let residual = self.system.apply(param)?;
+        let jacobian = self.system.jacobian(param)?;
+        Ok(jacobian.transpose() * residual)
+    }
+}
@@ -3,13 +3,17 @@ use structopt::StructOpt;
 use nalgebra::{DMatrix, DVector};
 use jasmin_optimization::{
     functions::Function,
+    functions::powell::PowellBadlyScaled,
     functions::quadratic::Quadratic,
     functions::rosenbrock::Rosenbrock2D,
+    functions::system::System,
     solvers::steepest_descent::SteepestDescent,
     solvers::newton::Newton,
     solvers::newton::NewtonWithModifications,
     solvers::quasinewton::Bfgs,
     solvers::newton::NewtonDogleg,
+    solvers::derivativefree::{CmaEs, InterpolationTrustRegion, NelderMead, Pattern, PatternSearch},
+    solvers::equations::{Broyden, BroydenUpdate, InitialJacobian, NewtonSystem, PowellHybrid},
 };
 
 // TODO
@@ -27,29 +31,70 @@ use jasmin_optimization::{
 #[structopt(name = "jasmin", about = "Some experiments with numerical optimization.")]
 struct Opt {
     solver : String,
-    function : String
+    function : String,
+    /// Maximum number of iterations of the solvers of functions
+    #[structopt(long, default_value = "100")]
+    max_iters : u64,
 }
 
 macro_rules! solve {
-    ($cost:expr, $solver:expr, $x0:expr) => {
+    ($cost:expr, $solver:expr, $x0:expr, $max_iters:expr) => {
         let res = if $solver == "newton" {
-            $cost.solve(Newton::new(), $x0)
+            $cost.minimize(Newton::new(), $x0, $max_iters)
         }
         else if $solver == "modified" {
-            $cost.solve(NewtonWithModifications::new(), $x0)
+            $cost.minimize(NewtonWithModifications::new(), $x0, $max_iters)
         }
         else if $solver == "bfgs" {
             let hessian = $cost.hessian(&$x0).unwrap();
-            $cost.solve(Bfgs::new(&hessian).unwrap(), $x0)
+            $cost.minimize(Bfgs::new(&hessian).unwrap(), $x0, $max_iters)
         }
         else if $solver == "dogleg" {
-            $cost.solve(NewtonDogleg::new(10.0), $x0)
+            $cost.minimize(NewtonDogleg::new(10.0), $x0, $max_iters)
+        }
+        else if $solver == "neldermead" {
+            $cost.minimize(NelderMead::new(1.0).with_restarts(2), $x0, $max_iters)
+        }
+        else if $solver == "patternsearch" {
+            $cost.minimize(PatternSearch::new(Pattern::Compass, 1.0), $x0, $max_iters)
+        }
+        else if $solver == "interpolation" {
+            $cost.minimize(InterpolationTrustRegion::new(0.1), $x0, $max_iters)
+        }
+        else if $solver == "cmaes" {
+            $cost.minimize(CmaEs::new(1.0), $x0, $max_iters)
         }
         else
         {
-            $cost.solve(SteepestDescent::new(), $x0)
+            $cost.minimize(SteepestDescent::new(), $x0, $max_iters)
         };
-        println!("{}", res);
+
+        match res {
+            Ok(res) => println!("{}", res),
+            Err(err) => println!("{}", err),
+        }
+    };
+}
+
+macro_rules! solve_system {
+    ($system:expr, $solver:expr, $x0:expr) => {
+        let res = if $solver == "newton" {
+            $system.solve(NewtonSystem::new(1E-10), $x0)
+        }
+        else if $solver == "broyden" {
+            let broyden = Broyden::new(BroydenUpdate::Good, InitialJacobian::Exact, 1E-10)
+                .restart_on_stall();
+            $system.solve(broyden, $x0)
+        }
+        else
+        {
+            $system.solve(PowellHybrid::new(1E-10), $x0)
+        };
+
+        match res {
+            Ok(res) => println!("{}", res),
+            Err(err) => println!("{}", err),
+        }
     };
 }
 
@@ -61,7 +106,7 @@ fn main() {
         let cost = Rosenbrock2D::new(1.0, 100.0);
         let x0 = DVector::from_row_slice(&[-1.2, 1.0]);
 
-        solve!(cost, opt.solver, x0);
+        solve!(cost, opt.solver, x0, opt.max_iters);
     }
     else if opt.function == "quadratic" {
         let cost = Quadratic::new(
@@ -75,6 +120,12 @@ fn main() {
         );
         let x0 = DVector::from_row_slice(&[-20.0, 12.0, 2.71]);
 
-        solve!(cost, opt.solver, x0);
+        solve!(cost, opt.solver, x0, opt.max_iters);
+    }
+    else if opt.function == "powell" {
+        let system = PowellBadlyScaled::new();
+        let x0 = DVector::from_row_slice(&[0.0, 1.0]);
+
+        solve_system!(system, opt.solver, x0);
     }
 }
@@ -0,0 +1,268 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+
+use crate::functions::{converged, Function};
+use crate::functions::constraints::{ConstrainedProblem, Constraints};
+use crate::solvers::constrained::ConstrainedReport;
+
+static PENALTY : f64 = 10.0;
+static INCREASE : f64 = 100.0;
+static TOLERANCE : f64 = 1E-6;
+static MAX_OUTER_ITERS : usize = 30;
+static MAX_INNER_ITERS : u64 = 1000;
+
+/// Augmented Lagrangian function
+///
+///  L_A(x; λ, μ) = f(x) + λ_Eᵀ c_E(x) + μ/2 |c_E(x)|²
+///                 + 1/(2μ) Σ (max(λ_I + μ c_I(x), 0)² - λ_I²),
+///
+/// where the inequalities are handled as in Powell, Hestenes and Rockafellar.
+/// Its Hessian is approximated by ∇²f(x) + μ (J_Eᵀ J_E + J_Aᵀ J_A),
+/// where A are the inequalities with λ_I + μ c_I(x) > 0.
+#[derive(Clone)]
+pub struct AugmentedLagrangianFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    problem: &'a ConstrainedProblem<O, C>,
+    equality_multipliers: DVector<f64>,
+    inequality_multipliers: DVector<f64>,
+    penalty: f64,
+}
+
+impl<'a, O, C> AugmentedLagrangianFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    pub fn new(
+        problem: &'a ConstrainedProblem<O, C>,
+        equality_multipliers: DVector<f64>,
+        inequality_multipliers: DVector<f64>,
+        penalty: f64
+    ) -> Self {
+        AugmentedLagrangianFunction { problem, equality_multipliers, inequality_multipliers, penalty }
+    }
+
+    /// λ_E + μ c_E(x), the first order estimate of the multipliers of the equalities.
+    fn shifted_equality(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        Ok(&self.equality_multipliers + self.penalty * self.problem.equality(param)?)
+    }
+
+    /// max(λ_I + μ c_I(x), 0), the first order estimate of the multipliers of the inequalities.
+    fn shifted_inequality(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        Ok((&self.inequality_multipliers + self.penalty * self.problem.inequality(param)?)
+            .map(|lambda| lambda.max(0.0)))
+    }
+}
+
+impl<'a, O, C> ArgminOp for AugmentedLagrangianFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    type Param = DVector<f64>;
+    type Output = f64;
+    type Hessian = DMatrix<f64>;
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let equality = self.problem.equality(param)?;
+        let shifted = self.shifted_inequality(param)?;
+
+        Ok(self.problem.function().apply(param)?
+            + self.equality_multipliers.dot(&equality)
+            + 0.5 * self.penalty * equality.norm_squared()
+            + (shifted.norm_squared() - self.inequality_multipliers.norm_squared()) / (2.0 * self.penalty))
+    }
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        Ok(self.problem.function().gradient(param)?
+            + self.problem.equality_jacobian(param)?.transpose() * self.shifted_equality(param)?
+            + self.problem.inequality_jacobian(param)?.transpose() * self.shifted_inequality(param)?)
+    }
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let jacobian_e = self.problem.equality_jacobian(param)?;
+        let mut jacobian_i = self.problem.inequality_jacobian(param)?;
+        let shifted = self.shifted_inequality(param)?;
+        for i in 0 .. shifted.len() {
+            if shifted[i] == 0.0 {
+                jacobian_i.row_mut(i).fill(0.0);
+            }
+        }
+
+        Ok(self.problem.function().hessian(param)?
+            + self.penalty * jacobian_e.tr_mul(&jacobian_e)
+            + self.penalty * jacobian_i.tr_mul(&jacobian_i))
+    }
+}
+
+impl<'a, O, C> Function for AugmentedLagrangianFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{}
+
+/// Augmented Lagrangian method: L_A(x; λ, μ) is minimized by an
+/// unconstrained solver, from the previous minimizer, and then
+///
+/// - if the infeasibility |(c_E(x), max(c_I(x), -λ_I/μ))|∞ is smaller than η,
+///   the multipliers are updated to λ_E + μ c_E(x) and max(λ_I + μ c_I(x), 0),
+///   and η is decreased to η / μ^0.9;
+/// - otherwise the penalty is increased to 100 μ and η is reset to 1 / μ^0.1,
+///
+/// until the infeasibility is smaller than the tolerance.
+/// The inner problems are solved to the tolerance of the unconstrained solver.
+///
+/// References:
+///
+/// Andrew R. Conn, Nicholas I. M. Gould and Philippe L. Toint (1992).
+/// LANCELOT: A Fortran Package for Large-Scale Nonlinear Optimization.
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization. [chapter 17.3-17.4]
+pub struct AugmentedLagrangian {
+    penalty: f64,
+    increase: f64,
+    tolerance: f64,
+    max_outer_iters: usize,
+    max_inner_iters: u64,
+}
+
+impl AugmentedLagrangian {
+    pub fn new() -> Self {
+        AugmentedLagrangian {
+            penalty: PENALTY,
+            increase: INCREASE,
+            tolerance: TOLERANCE,
+            max_outer_iters: MAX_OUTER_ITERS,
+            max_inner_iters: MAX_INNER_ITERS,
+        }
+    }
+
+    /// Initial penalty μ_0, multiplied by increase when the infeasibility
+    /// doesn't decrease enough.
+    pub fn with_penalty(mut self, penalty: f64, increase: f64) -> Self {
+        self.penalty = penalty;
+        self.increase = increase;
+        self
+    }
+
+    /// Tolerance on the infeasibility.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_outer_iters(mut self, max_outer_iters: usize) -> Self {
+        self.max_outer_iters = max_outer_iters;
+        self
+    }
+
+    /// Maximum number of iterations of every unconstrained solve.
+    pub fn with_max_inner_iters(mut self, max_inner_iters: u64) -> Self {
+        self.max_inner_iters = max_inner_iters;
+        self
+    }
+
+    /// Solve the inner problems with solvers created by solver.
+    pub fn run<'a, O, C, S, G>(&self, problem: &'a ConstrainedProblem<O, C>, mut solver: G, param: DVector<f64>)
+        -> Result<ConstrainedReport, Error>
+    where
+        O: Function + Clone,
+        C: Constraints,
+        S: Solver<AugmentedLagrangianFunction<'a, O, C>>,
+        G: FnMut() -> S
+    {
+        let mut param = param;
+        let mut penalty = self.penalty;
+        let mut lambda_e = DVector::zeros(problem.equality(&param)?.len());
+        let mut lambda_i = DVector::zeros(problem.inequality(&param)?.len());
+        let mut eta = 1.0 / penalty.powf(0.1);
+
+        for iter in 0 .. self.max_outer_iters {
+            let func = AugmentedLagrangianFunction::new(problem, lambda_e.clone(), lambda_i.clone(), penalty);
+            let res = func.clone().minimize(solver(), param, self.max_inner_iters)?;
+            if !converged(&res) {
+                return Err(Error::msg("The unconstrained solver didn't converge on the augmented Lagrangian."));
+            }
+            param = res.state.best_param;
+
+            let equality = problem.equality(&param)?;
+            let inequality = problem.inequality(&param)?;
+            let infeasibility = equality.iter()
+                .map(|c| c.abs())
+                .chain(inequality.iter().zip(lambda_i.iter()).map(|(c, lambda)| c.max(-lambda / penalty).abs()))
+                .fold(0.0, f64::max);
+
+            if infeasibility <= eta {
+                lambda_e = func.shifted_equality(&param)?;
+                lambda_i = func.shifted_inequality(&param)?;
+
+                if infeasibility <= self.tolerance {
+                    return Ok(ConstrainedReport {
+                        cost: problem.function().apply(&param)?,
+                        violation: problem.violation(&param)?,
+                        param,
+                        equality_multipliers: lambda_e,
+                        inequality_multipliers: lambda_i,
+                        penalty,
+                        outer_iterations: iter + 1,
+                    });
+                }
+                eta /= penalty.powf(0.9);
+            }
+            else {
+                penalty *= self.increase;
+                eta = 1.0 / penalty.powf(0.1);
+            }
+        }
+
+        Err(Error::msg("The augmented Lagrangian method reached the maximum number of outer iterations."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::fixtures::{closest_point, distance, Disk};
+    use crate::solvers::newton::{NewtonDogleg, NewtonWithModifications};
+    use crate::solvers::quasinewton::Bfgs;
+
+    fn check(report: &ConstrainedReport) {
+        let (expected, multiplier) = closest_point();
+        assert!((&report.param - expected).norm() < 1E-5);
+        assert!((report.inequality_multipliers[0] - multiplier).abs() < 1E-4);
+        assert!(report.violation <= 1E-6);
+    }
+
+    #[test]
+    fn test_augmented_lagrangian() {
+        let problem = ConstrainedProblem::new(distance()).with_nonlinear(Disk {});
+        let x0 = DVector::zeros(2);
+        let augmented_lagrangian = AugmentedLagrangian::new();
+
+        let report = augmented_lagrangian
+            .run(&problem, NewtonWithModifications::new, x0.clone())
+            .unwrap();
+        check(&report);
+
+        let report = augmented_lagrangian
+            .run(&problem, || NewtonDogleg::new(10.0), x0.clone())
+            .unwrap();
+        check(&report);
+
+        let report = augmented_lagrangian
+            .run(&problem, || Bfgs::new(&DMatrix::identity(2, 2)).unwrap(), x0.clone())
+            .unwrap();
+        check(&report);
+
+        // the unconstrained solver stops before converging
+        assert!(AugmentedLagrangian::new()
+            .with_max_inner_iters(2)
+            .run(&problem, || Bfgs::new(&DMatrix::identity(2, 2)).unwrap(), x0)
+            .is_err());
+    }
+}
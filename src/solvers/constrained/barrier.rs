@@ -0,0 +1,296 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+
+use crate::functions::{converged, Function};
+use crate::functions::constraints::{ConstrainedProblem, Constraints};
+use crate::solvers::constrained::ConstrainedReport;
+
+static BARRIER : f64 = 1.0;
+static DECREASE : f64 = 0.1;
+static TOLERANCE : f64 = 1E-8;
+static MAX_OUTER_ITERS : usize = 50;
+static MAX_INNER_ITERS : u64 = 1000;
+
+/// Logarithmic barrier function
+///
+///  P(x; μ) = f(x) - μ Σ log(-c_I(x)),
+///
+/// defined in the strict interior c_I(x) < 0 and +∞ elsewhere,
+/// so that the backtracking line search rejects infeasible trial points.
+/// Its Hessian is approximated by ∇²f(x) + μ Σ ∇c_i ∇c_iᵀ / c_i².
+#[derive(Clone)]
+pub struct BarrierFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    problem: &'a ConstrainedProblem<O, C>,
+    barrier: f64,
+}
+
+impl<'a, O, C> BarrierFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    pub fn new(problem: &'a ConstrainedProblem<O, C>, barrier: f64) -> Self {
+        BarrierFunction { problem, barrier }
+    }
+
+    /// The inequalities c_I(x), if x is strictly feasible.
+    fn interior(&self, param: &DVector<f64>) -> Result<Option<DVector<f64>>, Error> {
+        let inequality = self.problem.inequality(param)?;
+        if inequality.iter().all(|&c| c < 0.0) {
+            Ok(Some(inequality))
+        }
+        else {
+            Ok(None)
+        }
+    }
+
+    /// μ / -c_I(x), the first order estimate of the multipliers.
+    fn multipliers(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        let inequality = self.interior(param)?
+            .ok_or(Error::msg("The barrier function is not defined outside the strict interior."))?;
+
+        Ok(inequality.map(|c| -self.barrier / c))
+    }
+}
+
+impl<'a, O, C> ArgminOp for BarrierFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    type Param = DVector<f64>;
+    type Output = f64;
+    type Hessian = DMatrix<f64>;
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        match self.interior(param)? {
+            Some(inequality) => Ok(self.problem.function().apply(param)?
+                - self.barrier * inequality.iter().map(|c| (-c).ln()).sum::<f64>()),
+            None => Ok(f64::INFINITY),
+        }
+    }
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        let multipliers = self.multipliers(param)?;
+
+        Ok(self.problem.function().gradient(param)?
+            + self.problem.inequality_jacobian(param)?.transpose() * multipliers)
+    }
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let multipliers = self.multipliers(param)?;
+        let mut jacobian = self.problem.inequality_jacobian(param)?;
+        // μ / c_i² = λ_i² / μ
+        for i in 0 .. multipliers.len() {
+            jacobian.row_mut(i).scale_mut(multipliers[i] / self.barrier.sqrt());
+        }
+
+        Ok(self.problem.function().hessian(param)? + jacobian.tr_mul(&jacobian))
+    }
+}
+
+impl<'a, O, C> Function for BarrierFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{}
+
+/// Logarithmic barrier method for problems with inequality constraints only:
+/// the barrier function P(x; μ) is minimized by an unconstrained solver,
+/// from the previous minimizer, for a decreasing sequence of barrier
+/// parameters μ, until the duality gap m μ of the convex case is smaller
+/// than the tolerance. The starting point must be strictly feasible,
+/// and so are all the iterates.
+/// The multipliers are estimated by λ_I = μ / -c_I(x).
+///
+/// The Hessian of the barrier function becomes ill-conditioned as μ goes
+/// to zero, but it stays positive definite near the minimizers,
+/// and the modified Cholesky factorization leaves it unmodified.
+///
+/// References:
+///
+/// Anthony V. Fiacco and Garth P. McCormick (1968).
+/// Nonlinear Programming: Sequential Unconstrained Minimization Techniques.
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization. [chapter 19.6]
+pub struct LogBarrier {
+    barrier: f64,
+    decrease: f64,
+    tolerance: f64,
+    max_outer_iters: usize,
+    max_inner_iters: u64,
+}
+
+impl LogBarrier {
+    pub fn new() -> Self {
+        LogBarrier {
+            barrier: BARRIER,
+            decrease: DECREASE,
+            tolerance: TOLERANCE,
+            max_outer_iters: MAX_OUTER_ITERS,
+            max_inner_iters: MAX_INNER_ITERS,
+        }
+    }
+
+    /// Initial barrier parameter μ_0, multiplied by decrease after every outer iteration.
+    pub fn with_barrier(mut self, barrier: f64, decrease: f64) -> Self {
+        self.barrier = barrier;
+        self.decrease = decrease;
+        self
+    }
+
+    /// Tolerance on the duality gap m μ, where m is the number of inequalities.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_outer_iters(mut self, max_outer_iters: usize) -> Self {
+        self.max_outer_iters = max_outer_iters;
+        self
+    }
+
+    /// Maximum number of iterations of every unconstrained solve.
+    pub fn with_max_inner_iters(mut self, max_inner_iters: u64) -> Self {
+        self.max_inner_iters = max_inner_iters;
+        self
+    }
+
+    /// Solve the barrier problems with solvers created by solver,
+    /// from the strictly feasible param.
+    pub fn run<'a, O, C, S, G>(&self, problem: &'a ConstrainedProblem<O, C>, mut solver: G, param: DVector<f64>)
+        -> Result<ConstrainedReport, Error>
+    where
+        O: Function + Clone,
+        C: Constraints,
+        S: Solver<BarrierFunction<'a, O, C>>,
+        G: FnMut() -> S
+    {
+        if !problem.equality(&param)?.is_empty() {
+            return Err(Error::msg("The barrier method doesn't handle equality constraints."));
+        }
+        if !(self.barrier > 0.0) || !(self.decrease > 0.0 && self.decrease < 1.0) {
+            return Err(Error::msg("The barrier parameter must be positive and decrease must be in (0, 1)."));
+        }
+        if BarrierFunction::new(problem, self.barrier).interior(&param)?.is_none() {
+            return Err(Error::msg("The starting point must be strictly feasible."));
+        }
+
+        let mut param = param;
+        let mut barrier = self.barrier;
+        let m = problem.inequality(&param)?.len() as f64;
+
+        for iter in 0 .. self.max_outer_iters {
+            let func = BarrierFunction::new(problem, barrier);
+            let res = func.clone().minimize(solver(), param, self.max_inner_iters)?;
+            if !converged(&res) {
+                return Err(Error::msg("The unconstrained solver didn't converge on the barrier function."));
+            }
+            param = res.state.best_param;
+
+            if m * barrier <= self.tolerance {
+                return Ok(ConstrainedReport {
+                    cost: problem.function().apply(&param)?,
+                    equality_multipliers: DVector::zeros(0),
+                    inequality_multipliers: func.multipliers(&param)?,
+                    violation: problem.violation(&param)?,
+                    param,
+                    penalty: barrier,
+                    outer_iterations: iter + 1,
+                });
+            }
+            barrier *= self.decrease;
+        }
+
+        Err(Error::msg("The barrier method reached the maximum number of outer iterations."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::constraints::LinearConstraints;
+    use crate::functions::fixtures::{closest_point, distance, Disk};
+    use crate::solvers::newton::{Newton, NewtonDogleg, NewtonWithModifications};
+
+    #[test]
+    fn test_log_barrier() {
+        let problem = ConstrainedProblem::new(distance()).with_nonlinear(Disk {});
+        let (expected, multiplier) = closest_point();
+
+        let report = LogBarrier::new()
+            .run(&problem, Newton::new, DVector::zeros(2))
+            .unwrap();
+        assert!((&report.param - &expected).norm() < 1E-6);
+        assert!((report.inequality_multipliers[0] - multiplier).abs() < 1E-5);
+        assert_eq!(report.violation, 0.0);
+
+        let report = LogBarrier::new()
+            .run(&problem, NewtonWithModifications::new, DVector::zeros(2))
+            .unwrap();
+        assert!((&report.param - &expected).norm() < 1E-6);
+        assert_eq!(report.violation, 0.0);
+
+        let report = LogBarrier::new()
+            .run(&problem, || NewtonDogleg::new(1.0), DVector::zeros(2))
+            .unwrap();
+        assert!((&report.param - &expected).norm() < 1E-6);
+        assert_eq!(report.violation, 0.0);
+    }
+
+    #[test]
+    fn test_two_active_constraints() {
+        // x2 <= 0.4 cuts the disk before its closest point
+        let problem = ConstrainedProblem::new(distance())
+            .with_linear_inequalities(LinearConstraints::new(
+                DMatrix::from_row_slice(1, 2, &[0f64, 1f64]),
+                DVector::from_row_slice(&[0.4]),
+            ).unwrap())
+            .with_nonlinear(Disk {});
+
+        let report = LogBarrier::new()
+            .run(&problem, Newton::new, DVector::zeros(2))
+            .unwrap();
+
+        let x1 = 0.84f64.sqrt();
+        assert!((&report.param - DVector::from_row_slice(&[x1, 0.4])).norm() < 1E-6);
+
+        // the Hessian of the barrier function is badly scaled near both constraints
+        let modified = LogBarrier::new()
+            .run(&problem, NewtonWithModifications::new, DVector::zeros(2))
+            .unwrap();
+        assert!((&modified.param - &report.param).norm() < 1E-6);
+        // 2(x - (2, 1)) + λ_1 (0, 1) + λ_2 2x = 0
+        let lambda_2 = (2.0 - x1) / x1;
+        let lambda_1 = 1.2 - 0.8 * lambda_2;
+        assert!((report.inequality_multipliers[0] - lambda_1).abs() < 1E-5);
+        assert!((report.inequality_multipliers[1] - lambda_2).abs() < 1E-5);
+        assert!(report.inequality_multipliers.iter().all(|&lambda| lambda > 0.0));
+    }
+
+    #[test]
+    fn test_infeasible_start() {
+        let problem = ConstrainedProblem::new(distance()).with_nonlinear(Disk {});
+
+        assert!(LogBarrier::new()
+            .run(&problem, Newton::new, DVector::from_row_slice(&[1.0, 0.0]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_max_inner_iters() {
+        let problem = ConstrainedProblem::new(distance()).with_nonlinear(Disk {});
+
+        // a single Newton step doesn't minimize the barrier function
+        assert!(LogBarrier::new()
+            .with_max_inner_iters(1)
+            .run(&problem, Newton::new, DVector::zeros(2))
+            .is_err());
+    }
+}
@@ -0,0 +1,16 @@
+//! Drivers for constrained problems.
+//!
+//! The constraints provide only their Jacobians, so the Hessians of the
+//! penalty, augmented Lagrangian and barrier functions leave out the second
+//! derivatives of the constraints, as Gauss-Newton does for least squares:
+//! they are exact for linear constraints only.
+
+mod augmented_lagrangian;
+mod barrier;
+mod penalty;
+mod sqp;
+
+pub use augmented_lagrangian::*;
+pub use barrier::*;
+pub use penalty::*;
+pub use sqp::*;
@@ -0,0 +1,230 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+
+use crate::functions::{converged, Function};
+use crate::functions::constraints::{ConstrainedProblem, Constraints};
+
+static PENALTY : f64 = 1.0;
+static INCREASE : f64 = 10.0;
+static TOLERANCE : f64 = 1E-6;
+static MAX_OUTER_ITERS : usize = 20;
+static MAX_INNER_ITERS : u64 = 1000;
+
+/// Result of a driver for constrained problems.
+#[derive(Clone, Debug)]
+pub struct ConstrainedReport {
+    pub param: DVector<f64>,
+    pub cost: f64,
+    /// Estimates of the Lagrange multipliers, in the order of
+    /// ConstrainedProblem::equality and ConstrainedProblem::inequality.
+    pub equality_multipliers: DVector<f64>,
+    pub inequality_multipliers: DVector<f64>,
+    /// ℓ1 norm of the constraint violation.
+    pub violation: f64,
+    /// Final penalty, or barrier, parameter.
+    pub penalty: f64,
+    pub outer_iterations: usize,
+}
+
+/// Quadratic penalty function
+///
+///  Q(x; μ) = f(x) + μ/2 (Σ c_E(x)² + Σ max(c_I(x), 0)²).
+///
+/// Its Hessian is approximated by ∇²f(x) + μ (J_Eᵀ J_E + J_Aᵀ J_A),
+/// where A are the violated inequalities.
+#[derive(Clone)]
+pub struct PenaltyFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    problem: &'a ConstrainedProblem<O, C>,
+    penalty: f64,
+}
+
+impl<'a, O, C> PenaltyFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    pub fn new(problem: &'a ConstrainedProblem<O, C>, penalty: f64) -> Self {
+        PenaltyFunction { problem, penalty }
+    }
+
+    /// The violated part max(c_I(x), 0) of the inequalities.
+    fn violated(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        Ok(self.problem.inequality(param)?.map(|c| c.max(0.0)))
+    }
+}
+
+impl<'a, O, C> ArgminOp for PenaltyFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    type Param = DVector<f64>;
+    type Output = f64;
+    type Hessian = DMatrix<f64>;
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let equality = self.problem.equality(param)?;
+        let violated = self.violated(param)?;
+
+        Ok(self.problem.function().apply(param)?
+            + 0.5 * self.penalty * (equality.norm_squared() + violated.norm_squared()))
+    }
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+        let equality = self.problem.equality(param)?;
+        let violated = self.violated(param)?;
+
+        Ok(self.problem.function().gradient(param)?
+            + self.penalty * self.problem.equality_jacobian(param)?.transpose() * equality
+            + self.penalty * self.problem.inequality_jacobian(param)?.transpose() * violated)
+    }
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let jacobian_e = self.problem.equality_jacobian(param)?;
+        let mut jacobian_i = self.problem.inequality_jacobian(param)?;
+        let violated = self.violated(param)?;
+        for i in 0 .. violated.len() {
+            if violated[i] == 0.0 {
+                jacobian_i.row_mut(i).fill(0.0);
+            }
+        }
+
+        Ok(self.problem.function().hessian(param)?
+            + self.penalty * jacobian_e.tr_mul(&jacobian_e)
+            + self.penalty * jacobian_i.tr_mul(&jacobian_i))
+    }
+}
+
+impl<'a, O, C> Function for PenaltyFunction<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{}
+
+/// Quadratic penalty method: the penalty function Q(x; μ) is minimized
+/// by an unconstrained solver, from the previous minimizer,
+/// for an increasing sequence of penalties μ, until the violation of the
+/// constraints is smaller than the tolerance.
+/// The multipliers are estimated by λ_E = μ c_E(x) and λ_I = μ max(c_I(x), 0).
+///
+/// Reference:
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization. [chapter 17.1]
+pub struct QuadraticPenalty {
+    penalty: f64,
+    increase: f64,
+    tolerance: f64,
+    max_outer_iters: usize,
+    max_inner_iters: u64,
+}
+
+impl QuadraticPenalty {
+    pub fn new() -> Self {
+        QuadraticPenalty {
+            penalty: PENALTY,
+            increase: INCREASE,
+            tolerance: TOLERANCE,
+            max_outer_iters: MAX_OUTER_ITERS,
+            max_inner_iters: MAX_INNER_ITERS,
+        }
+    }
+
+    /// Initial penalty μ_0, multiplied by increase after every outer iteration.
+    pub fn with_penalty(mut self, penalty: f64, increase: f64) -> Self {
+        self.penalty = penalty;
+        self.increase = increase;
+        self
+    }
+
+    /// Tolerance on the ℓ1 norm of the constraint violation.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_outer_iters(mut self, max_outer_iters: usize) -> Self {
+        self.max_outer_iters = max_outer_iters;
+        self
+    }
+
+    /// Maximum number of iterations of every unconstrained solve.
+    pub fn with_max_inner_iters(mut self, max_inner_iters: u64) -> Self {
+        self.max_inner_iters = max_inner_iters;
+        self
+    }
+
+    /// Solve the penalized problems with solvers created by solver.
+    pub fn run<'a, O, C, S, G>(&self, problem: &'a ConstrainedProblem<O, C>, mut solver: G, param: DVector<f64>)
+        -> Result<ConstrainedReport, Error>
+    where
+        O: Function + Clone,
+        C: Constraints,
+        S: Solver<PenaltyFunction<'a, O, C>>,
+        G: FnMut() -> S
+    {
+        let mut param = param;
+        let mut penalty = self.penalty;
+
+        for iter in 0 .. self.max_outer_iters {
+            let res = PenaltyFunction::new(problem, penalty).minimize(solver(), param, self.max_inner_iters)?;
+            if !converged(&res) {
+                return Err(Error::msg("The unconstrained solver didn't converge on the penalty function."));
+            }
+            param = res.state.best_param;
+
+            let violation = problem.violation(&param)?;
+            if violation <= self.tolerance {
+                return Ok(ConstrainedReport {
+                    cost: problem.function().apply(&param)?,
+                    equality_multipliers: penalty * problem.equality(&param)?,
+                    inequality_multipliers: penalty * problem.inequality(&param)?.map(|c| c.max(0.0)),
+                    param,
+                    violation,
+                    penalty,
+                    outer_iterations: iter + 1,
+                });
+            }
+            penalty *= self.increase;
+        }
+
+        Err(Error::msg("The quadratic penalty method reached the maximum number of outer iterations."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::constraints::LinearConstraints;
+    use crate::functions::fixtures::distance;
+    use crate::solvers::newton::NewtonWithModifications;
+
+    #[test]
+    fn test_quadratic_penalty() {
+        // x1 + x2 = 1, x1 <= 0.5
+        let problem = ConstrainedProblem::new(distance())
+            .with_linear_equalities(LinearConstraints::new(
+                DMatrix::from_row_slice(1, 2, &[1f64, 1f64]),
+                DVector::from_row_slice(&[1.0]),
+            ).unwrap())
+            .with_linear_inequalities(LinearConstraints::new(
+                DMatrix::from_row_slice(1, 2, &[1f64, 0f64]),
+                DVector::from_row_slice(&[0.5]),
+            ).unwrap());
+
+        let report = QuadraticPenalty::new()
+            .run(&problem, NewtonWithModifications::new, DVector::zeros(2))
+            .unwrap();
+
+        assert!(report.violation <= 1E-6);
+        assert!((&report.param - DVector::from_row_slice(&[0.5, 0.5])).norm() < 1E-5);
+        // 2(x - (2, 1)) + λ_E (1, 1) + λ_I (1, 0) = 0
+        assert!((report.equality_multipliers[0] - 1.0).abs() < 1E-3);
+        assert!((report.inequality_multipliers[0] - 2.0).abs() < 1E-3);
+    }
+}
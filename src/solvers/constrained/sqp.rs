@@ -0,0 +1,311 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+
+use crate::functions::Function;
+use crate::functions::constraints::{ConstrainedProblem, Constraints, LinearConstraints};
+use crate::functions::quadratic::Quadratic;
+use crate::solvers::qp::ActiveSet;
+use crate::steplength::backtracking;
+
+static TOLERANCE : f64 = 1E-6;
+static MAX_ITERS : usize = 100;
+
+/// Lower bound of sᵀr / sᵀBs in the damped BFGS update.
+static DAMPING : f64 = 0.2;
+
+/// Fraction ρ of the decrease of the merit function required
+/// from the penalty term, as in (18.36).
+static RHO : f64 = 0.5;
+
+/// Relative margin of the penalty above its lower bound.
+static PENALTY_MARGIN : f64 = 0.1;
+
+/// Result of the SQP method, with the residuals of the KKT conditions.
+#[derive(Clone, Debug)]
+pub struct SqpReport {
+    pub param: DVector<f64>,
+    pub cost: f64,
+    /// Multipliers of the last QP subproblem, in the order of
+    /// ConstrainedProblem::equality and ConstrainedProblem::inequality.
+    pub equality_multipliers: DVector<f64>,
+    pub inequality_multipliers: DVector<f64>,
+    /// |∇f(x) + J_Eᵀλ_E + J_Iᵀλ_I|∞
+    pub stationarity: f64,
+    /// |(c_E(x), max(c_I(x), 0))|∞
+    pub feasibility: f64,
+    /// max |λ_i c_i(x)| over the inequalities.
+    pub complementarity: f64,
+    /// Final penalty of the ℓ1 merit function.
+    pub penalty: f64,
+    pub iterations: usize,
+}
+
+/// ℓ1 merit function
+///
+///  φ(x; μ) = f(x) + μ (Σ |c_E(x)| + Σ max(c_I(x), 0)),
+///
+/// which is not differentiable, so only its value is available.
+#[derive(Clone)]
+pub struct L1Merit<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    problem: &'a ConstrainedProblem<O, C>,
+    penalty: f64,
+}
+
+impl<'a, O, C> L1Merit<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    pub fn new(problem: &'a ConstrainedProblem<O, C>, penalty: f64) -> Self {
+        L1Merit { problem, penalty }
+    }
+}
+
+impl<'a, O, C> ArgminOp for L1Merit<'a, O, C>
+where
+    O: Function,
+    C: Constraints
+{
+    type Param = DVector<f64>;
+    type Output = f64;
+    type Hessian = ();
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self.problem.function().apply(param)? + self.penalty * self.problem.violation(param)?)
+    }
+}
+
+/// Line search SQP method: at every iterate the step p solves the QP
+///
+///  min ½pᵀBp + ∇f(x)ᵀp  s.t.  J_E(x) p + c_E(x) = 0,  J_I(x) p + c_I(x) <= 0
+///
+/// with the active-set method, and its multipliers are the new estimates.
+/// The step length is given by a backtracking line search on the ℓ1 merit
+/// function, whose penalty is kept above the multipliers and the bound (18.36),
+/// so that p is a descent direction.
+/// B approximates the Hessian of the Lagrangian by the damped BFGS update,
+/// and stays positive definite.
+/// The iterations stop when the KKT residuals are smaller than the tolerance.
+///
+/// The linearized constraints must be consistent.
+///
+/// Reference:
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization. [chapter 18.3-18.4]
+pub struct Sqp {
+    tolerance: f64,
+    max_iters: usize,
+}
+
+impl Sqp {
+    pub fn new() -> Self {
+        Sqp {
+            tolerance: TOLERANCE,
+            max_iters: MAX_ITERS,
+        }
+    }
+
+    /// Tolerance on the KKT residuals.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_iters(mut self, max_iters: usize) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    pub fn solve<O, C>(&self, problem: &ConstrainedProblem<O, C>, param: DVector<f64>)
+        -> Result<SqpReport, Error>
+    where
+        O: Function,
+        C: Constraints
+    {
+        let n = param.len();
+        let mut param = param;
+        let mut hessian = DMatrix::identity(n, n);
+        let mut penalty = 0.0;
+        let qp_solver = ActiveSet::new();
+
+        for iter in 0 .. self.max_iters {
+            let grad = problem.function().gradient(&param)?;
+            let equality = problem.equality(&param)?;
+            let inequality = problem.inequality(&param)?;
+
+            let subproblem = ConstrainedProblem::new(Quadratic::new(hessian.clone(), grad.clone(), 0.0))
+                .with_linear_equalities(LinearConstraints::new(problem.equality_jacobian(&param)?, -&equality)?)
+                .with_linear_inequalities(LinearConstraints::new(problem.inequality_jacobian(&param)?, -&inequality)?);
+            let solution = qp_solver.solve(&subproblem, DVector::zeros(n))?;
+            let (step, lambda_e, lambda_i) =
+                (solution.param, solution.equality_multipliers, solution.inequality_multipliers);
+
+            let stationarity = problem.lagrangian_gradient(&param, &lambda_e, &lambda_i)?.amax();
+            let feasibility = equality.iter().map(|c| c.abs())
+                .chain(inequality.iter().map(|c| c.max(0.0)))
+                .fold(0.0, f64::max);
+            let complementarity = inequality.iter().zip(lambda_i.iter())
+                .map(|(c, lambda)| (c * lambda).abs())
+                .fold(0.0, f64::max);
+
+            if stationarity.max(feasibility).max(complementarity) <= self.tolerance {
+                return Ok(SqpReport {
+                    cost: problem.function().apply(&param)?,
+                    param,
+                    equality_multipliers: lambda_e,
+                    inequality_multipliers: lambda_i,
+                    stationarity,
+                    feasibility,
+                    complementarity,
+                    penalty,
+                    iterations: iter,
+                });
+            }
+
+            // the directional derivative of the merit function along p is at most
+            // ∇fᵀp - μ |c(x)|₁, which (18.36) bounds by -ρ μ |c(x)|₁ - ½ pᵀBp
+            let violation = problem.violation(&param)?;
+            let mut min_penalty = lambda_e.iter().chain(lambda_i.iter()).fold(0.0, |max, lambda| lambda.abs().max(max));
+            if violation > 0.0 {
+                let model_decrease = grad.dot(&step) + 0.5 * step.dot(&(&hessian * &step));
+                min_penalty = min_penalty.max(model_decrease / ((1.0 - RHO) * violation));
+            }
+            if penalty < min_penalty {
+                penalty = (1.0 + PENALTY_MARGIN) * min_penalty;
+            }
+
+            let merit = L1Merit::new(problem, penalty);
+            let step_length = backtracking::search_with_slope(
+                &merit, &param, merit.apply(&param)?, grad.dot(&step) - penalty * violation, &step, 1.0
+            )?;
+            let next_param = &param + step_length * &step;
+
+            let s = &next_param - &param;
+            let y = problem.lagrangian_gradient(&next_param, &lambda_e, &lambda_i)?
+                - problem.lagrangian_gradient(&param, &lambda_e, &lambda_i)?;
+            damped_bfgs_update(&mut hessian, &s, &y);
+
+            param = next_param;
+        }
+
+        Err(Error::msg("The SQP method reached the maximum number of iterations."))
+    }
+}
+
+/// Damped BFGS update (Procedure 18.2): y is replaced by
+/// r = θy + (1 - θ)Bs, with θ such that sᵀr >= 0.2 sᵀBs,
+/// so that B stays positive definite.
+fn damped_bfgs_update(hessian: &mut DMatrix<f64>, s: &DVector<f64>, y: &DVector<f64>) {
+    let bs = &*hessian * s;
+    let s_bs = s.dot(&bs);
+    if s_bs <= 0.0 {
+        return;
+    }
+
+    let s_y = s.dot(y);
+    let theta = if s_y >= DAMPING * s_bs {
+        1.0
+    }
+    else {
+        (1.0 - DAMPING) * s_bs / (s_bs - s_y)
+    };
+    let r = theta * y + (1.0 - theta) * &bs;
+
+    *hessian += &r * r.transpose() / s.dot(&r) - &bs * bs.transpose() / s_bs;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::fixtures::{closest_point, distance, Disk};
+
+    /// exp(x1 x2 x3 x4 x5) - ½(x1³ + x2³ + 1)²
+    #[derive(Clone)]
+    struct Exponential {}
+
+    impl ArgminOp for Exponential {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = DMatrix<f64>;
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param.iter().product::<f64>().exp() - 0.5 * (param[0].powi(3) + param[1].powi(3) + 1.0).powi(2))
+        }
+
+        fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+            let exp = param.iter().product::<f64>().exp();
+            let cubic = param[0].powi(3) + param[1].powi(3) + 1.0;
+            let mut grad = DVector::from_fn(5, |i, _| {
+                exp * (0 .. 5).filter(|&j| j != i).map(|j| param[j]).product::<f64>()
+            });
+            grad[0] -= 3.0 * param[0].powi(2) * cubic;
+            grad[1] -= 3.0 * param[1].powi(2) * cubic;
+            Ok(grad)
+        }
+    }
+
+    impl Function for Exponential {}
+
+    /// |x|² = 10, x2 x3 = 5 x4 x5, x1³ + x2³ = -1
+    #[derive(Clone)]
+    struct Sphere {}
+
+    impl Constraints for Sphere {
+        fn equality(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+            Ok(DVector::from_row_slice(&[
+                param.norm_squared() - 10.0,
+                param[1] * param[2] - 5.0 * param[3] * param[4],
+                param[0].powi(3) + param[1].powi(3) + 1.0,
+            ]))
+        }
+
+        fn equality_jacobian(&self, param: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+            let mut jacobian = DMatrix::zeros(3, 5);
+            jacobian.row_mut(0).copy_from(&(2.0 * param).transpose());
+            jacobian[(1, 1)] = param[2];
+            jacobian[(1, 2)] = param[1];
+            jacobian[(1, 3)] = -5.0 * param[4];
+            jacobian[(1, 4)] = -5.0 * param[3];
+            jacobian[(2, 0)] = 3.0 * param[0].powi(2);
+            jacobian[(2, 1)] = 3.0 * param[1].powi(2);
+            Ok(jacobian)
+        }
+    }
+
+    #[test]
+    fn test_inequality() {
+        let problem = ConstrainedProblem::new(distance()).with_nonlinear(Disk {});
+
+        let report = Sqp::new().solve(&problem, DVector::zeros(2)).unwrap();
+
+        let (expected, multiplier) = closest_point();
+        assert!((&report.param - expected).norm() < 1E-5);
+        assert!((report.inequality_multipliers[0] - multiplier).abs() < 1E-5);
+        assert!(report.stationarity <= 1E-6);
+        assert!(report.feasibility <= 1E-6);
+        assert!(report.complementarity <= 1E-6);
+    }
+
+    #[test]
+    fn test_equality() {
+        // test problem of chapter 18 of Nocedal and Wright
+        let problem = ConstrainedProblem::new(Exponential {}).with_nonlinear(Sphere {});
+        let x0 = DVector::from_row_slice(&[-1.8, 1.7, 1.9, -0.8, -0.8]);
+
+        let report = Sqp::new().solve(&problem, x0).unwrap();
+
+        let expected = DVector::from_row_slice(&[-1.71714, 1.59571, 1.82725, -0.763643, -0.763643]);
+        assert!((&report.param - expected).amax() < 1E-5);
+        assert!((report.cost - 0.0539498).abs() < 1E-6);
+        assert!(report.stationarity <= 1E-6);
+        assert!(report.feasibility <= 1E-6);
+    }
+}
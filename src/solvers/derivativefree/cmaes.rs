@@ -0,0 +1,369 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+
+static TOLERANCE_X : f64 = 1E-12;
+static TOLERANCE_F : f64 = 1E-12;
+static MAX_CONDITION : f64 = 1E14;
+
+/// Strategy parameters depending on the dimension and on the population size.
+struct Parameters {
+    mu: usize,
+    weights: Vec<f64>,
+    mu_eff: f64,
+    c_sigma: f64,
+    d_sigma: f64,
+    c_c: f64,
+    c_1: f64,
+    c_mu: f64,
+    // expected norm of a standard normal vector
+    chi_n: f64,
+}
+
+impl Parameters {
+    fn new(n: usize, lambda: usize) -> Self {
+        let n = n as f64;
+        let mu = lambda / 2;
+
+        let weights: Vec<f64> = (1 ..= mu)
+            .map(|i| (mu as f64 + 0.5).ln() - (i as f64).ln())
+            .collect();
+        let sum: f64 = weights.iter().sum();
+        let weights: Vec<f64> = weights.iter().map(|w| w / sum).collect();
+        let mu_eff = 1.0 / weights.iter().map(|w| w * w).sum::<f64>();
+
+        let c_sigma = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let c_1 = 2.0 / ((n + 1.3).powi(2) + mu_eff);
+
+        Parameters {
+            mu,
+            weights,
+            mu_eff,
+            c_sigma,
+            d_sigma: 1.0 + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + c_sigma,
+            c_c: (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n),
+            c_1,
+            c_mu: (1.0 - c_1).min(2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0).powi(2) + mu_eff)),
+            chi_n: n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n)),
+        }
+    }
+}
+
+/// Standard normal sample, with the Box-Muller transform.
+fn standard_normal<R: Rng>(rng: &mut R) -> f64 {
+    let u1 = rng.gen::<f64>();
+    let u2 = rng.gen::<f64>();
+    (-2.0 * (1.0 - u1).ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+fn default_rng() -> StdRng {
+    StdRng::seed_from_u64(0)
+}
+
+/// Covariance matrix adaptation evolution strategy.
+///
+/// Every generation samples λ points from N(m, σ² C), and moves the mean
+/// to the weighted average of the μ = λ/2 best ones. The covariance C is
+/// adapted with the rank-one update, along the evolution path, and with
+/// the rank-μ update; the step size σ with cumulative step-size adaptation.
+/// Only the function values are used.
+///
+/// A run stops when the step size or the spread of the recent function
+/// values is below the tolerance, or when C is ill-conditioned.
+/// With IPOP restarts, the strategy then starts again from the initial
+/// point, with twice as large a population.
+///
+/// References:
+///
+/// Nikolaus Hansen (2016). The CMA Evolution Strategy: A Tutorial.
+///
+/// Anne Auger and Nikolaus Hansen (2005). A Restart CMA Evolution Strategy
+/// With Increasing Population Size.
+#[derive(Serialize, Deserialize)]
+pub struct CmaEs {
+    initial_sigma: f64,
+    initial_mean: DVector<f64>,
+    lambda: usize,
+    max_restarts: u32,
+    restarts: u32,
+    tolerance_x: f64,
+    tolerance_f: f64,
+    converged: bool,
+    generation: usize,
+    mean: DVector<f64>,
+    sigma: f64,
+    cov: DMatrix<f64>,
+    path_sigma: DVector<f64>,
+    path_c: DVector<f64>,
+    // best value of every generation of the current run
+    history: Vec<f64>,
+    #[serde(skip, default = "default_rng")]
+    rng: StdRng,
+}
+
+impl CmaEs {
+    /// Initial step size sigma, with the default population size
+    /// 4 + ⌊3 ln n⌋.
+    pub fn new(sigma: f64) -> Self {
+        CmaEs {
+            initial_sigma: sigma,
+            initial_mean: DVector::zeros(0),
+            lambda: 0,
+            max_restarts: 0,
+            restarts: 0,
+            tolerance_x: TOLERANCE_X,
+            tolerance_f: TOLERANCE_F,
+            converged: false,
+            generation: 0,
+            mean: DVector::zeros(0),
+            sigma,
+            cov: DMatrix::zeros(0, 0),
+            path_sigma: DVector::zeros(0),
+            path_c: DVector::zeros(0),
+            history: Vec::new(),
+            rng: default_rng(),
+        }
+    }
+
+    pub fn with_population_size(mut self, lambda: usize) -> Self {
+        self.lambda = lambda;
+        self
+    }
+
+    /// IPOP restarts, doubling the population size every time.
+    pub fn with_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    pub fn with_tolerances(mut self, tolerance_x: f64, tolerance_f: f64) -> Self {
+        self.tolerance_x = tolerance_x;
+        self.tolerance_f = tolerance_f;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    fn start(&mut self) {
+        let n = self.initial_mean.len();
+
+        self.generation = 0;
+        self.mean = self.initial_mean.clone();
+        self.sigma = self.initial_sigma;
+        self.cov = DMatrix::identity(n, n);
+        self.path_sigma = DVector::zeros(n);
+        self.path_c = DVector::zeros(n);
+        self.history.clear();
+    }
+
+    /// Whether the current run should stop, given the eigenvalues of C
+    /// and the sorted values of the last generation.
+    fn should_stop(&self, eigenvalues: &DVector<f64>, values: &[f64]) -> bool {
+        let n = self.mean.len() as f64;
+        let history_len = 10 + (30.0 * n / self.lambda as f64).ceil() as usize;
+
+        let history_range = if self.history.len() >= history_len {
+            let recent = &self.history[self.history.len() - history_len ..];
+            recent.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+                - recent.iter().cloned().fold(f64::INFINITY, f64::min)
+        }
+        else {
+            f64::INFINITY
+        };
+        let generation_range = values[values.len() - 1] - values[0];
+
+        let max_eigenvalue = eigenvalues.max();
+        let min_eigenvalue = eigenvalues.min();
+
+        (history_range <= self.tolerance_f && generation_range <= self.tolerance_f)
+            || self.sigma * max_eigenvalue.sqrt() <= self.tolerance_x
+            || max_eigenvalue > MAX_CONDITION * min_eigenvalue
+    }
+}
+
+impl<O> Solver<O> for CmaEs
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    const NAME: &'static str = "CmaEs";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        let n = param.len();
+
+        if self.lambda == 0 {
+            self.lambda = 4 + (3.0 * (n as f64).ln()).floor() as usize;
+        }
+        if self.lambda < 2 {
+            return Err(Error::msg("CMA-ES needs a population of at least 2 points."));
+        }
+
+        self.initial_mean = param.clone();
+        self.restarts = 0;
+        self.converged = false;
+        self.start();
+
+        let value = op.apply(&param)?;
+        Ok(Some(ArgminIterData::new().param(param).cost(value)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let n = self.mean.len();
+        let p = Parameters::new(n, self.lambda);
+        self.generation += 1;
+
+        // C = B D² Bᵀ
+        let eigen = self.cov.clone().symmetric_eigen();
+        let scales = eigen.eigenvalues.map(|v| v.max(0.0).sqrt());
+        let basis = eigen.eigenvectors;
+
+        // steps y = B D z, sorted by the value of m + σ y
+        let mut samples = Vec::with_capacity(self.lambda);
+        for _ in 0 .. self.lambda {
+            let rng = &mut self.rng;
+            let z = DVector::from_fn(n, |_, _| standard_normal(rng));
+            let step = &basis * z.component_mul(&scales);
+            let value = op.apply(&(&self.mean + self.sigma * &step))?;
+            samples.push((step, value));
+        }
+        samples.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let best = &self.mean + self.sigma * &samples[0].0;
+        let best_value = samples[0].1;
+
+        let weighted_step = (0 .. p.mu)
+            .fold(DVector::zeros(n), |acc, i| acc + p.weights[i] * &samples[i].0);
+        self.mean += self.sigma * &weighted_step;
+
+        // cumulative step-size adaptation, along the path of C^{-1/2} y
+        let inv_sqrt = &basis
+            * DMatrix::from_diagonal(&scales.map(|s| if s > 0.0 { 1.0 / s } else { 0.0 }))
+            * basis.transpose();
+        self.path_sigma = (1.0 - p.c_sigma) * &self.path_sigma
+            + (p.c_sigma * (2.0 - p.c_sigma) * p.mu_eff).sqrt() * (inv_sqrt * &weighted_step);
+
+        // stall the rank-one update while the step size grows too fast
+        let decay = 1.0 - (1.0 - p.c_sigma).powi(2 * self.generation as i32);
+        let h_sigma = if self.path_sigma.norm() / decay.sqrt() < (1.4 + 2.0 / (n as f64 + 1.0)) * p.chi_n {
+            1.0
+        }
+        else {
+            0.0
+        };
+
+        self.path_c = (1.0 - p.c_c) * &self.path_c
+            + h_sigma * (p.c_c * (2.0 - p.c_c) * p.mu_eff).sqrt() * &weighted_step;
+
+        let rank_one = &self.path_c * self.path_c.transpose()
+            + (1.0 - h_sigma) * p.c_c * (2.0 - p.c_c) * &self.cov;
+        let rank_mu = (0 .. p.mu)
+            .fold(DMatrix::zeros(n, n), |acc, i| acc + p.weights[i] * &samples[i].0 * samples[i].0.transpose());
+
+        let cov = (1.0 - p.c_1 - p.c_mu) * &self.cov + p.c_1 * rank_one + p.c_mu * rank_mu;
+        self.cov = 0.5 * (&cov + cov.transpose());
+
+        self.sigma *= ((p.c_sigma / p.d_sigma) * (self.path_sigma.norm() / p.chi_n - 1.0)).exp();
+        self.history.push(best_value);
+
+        let values: Vec<f64> = samples.iter().map(|(_, value)| *value).collect();
+        if self.should_stop(&eigen.eigenvalues, &values) {
+            if self.restarts < self.max_restarts {
+                self.restarts += 1;
+                self.lambda *= 2;
+                self.start();
+            }
+            else {
+                self.converged = true;
+            }
+        }
+
+        Ok(ArgminIterData::new().param(best).cost(best_value))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason
+    {
+        if self.converged {
+            TerminationReason::TargetToleranceReached
+        }
+        else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Σ 10^(6 i / (n - 1)) x_i², with condition number 10⁶.
+    #[derive(Clone)]
+    struct Ellipsoid {}
+
+    impl ArgminOp for Ellipsoid {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            let n = param.len();
+            Ok((0 .. n).map(|i| 10f64.powf(6.0 * i as f64 / (n - 1) as f64) * param[i].powi(2)).sum())
+        }
+    }
+
+    /// Rastrigin's function, with global minimum 0 at the origin
+    /// and a local minimum close to every integer point.
+    #[derive(Clone)]
+    struct Rastrigin {}
+
+    impl ArgminOp for Rastrigin {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param.iter().map(|x| 10.0 + x * x - 10.0 * (2.0 * PI * x).cos()).sum())
+        }
+    }
+
+    #[test]
+    fn test_ellipsoid() {
+        let x0 = DVector::from_element(5, 1.0);
+        let res = Executor::new(Ellipsoid {}, CmaEs::new(1.0).with_seed(0), x0)
+            .max_iters(5000)
+            .run()
+            .unwrap();
+
+        assert_eq!(TerminationReason::TargetToleranceReached, res.state.termination_reason);
+        assert!(res.state.best_cost < 1E-10);
+    }
+
+    #[test]
+    fn test_ipop_rastrigin() {
+        let x0 = DVector::from_row_slice(&[4.0, -3.0]);
+        let solver = CmaEs::new(2.0).with_restarts(6).with_seed(0);
+        let res = Executor::new(Rastrigin {}, solver, x0)
+            .max_iters(100000)
+            .run()
+            .unwrap();
+
+        assert_eq!(TerminationReason::TargetToleranceReached, res.state.termination_reason);
+        assert!(res.state.best_param.norm() < 1E-6);
+    }
+}
@@ -0,0 +1,359 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use trust_region_solver::Solverf64;
+use crate::solvers::newton::{cholesky, dogleg_step};
+use crate::solvers::trustregion::TrustRegion;
+
+static CHOL_DELTA : f64 = 1E-4;
+static CHOL_BETA : f64 = 100.0;
+
+/// Below this model gradient norm the interpolation set is shrunk
+/// around the current point, so that a small model gradient
+/// means a small gradient of the function.
+static CRITICALITY : f64 = 1E-2;
+
+/// Smallest radius of the interpolation set.
+static MIN_RADIUS : f64 = 1E-10;
+
+/// Derivative-free trust region method, with a quadratic model
+/// interpolating the function on (n + 1)(n + 2)/2 points.
+///
+/// The interpolation set starts as x, x ± r e_i and x + r (e_i + e_j).
+/// Every trial point replaces the point with the largest Lagrange
+/// polynomial at the trial point, weighted with its distance
+/// from the current iterate, which keeps the interpolation well posed.
+/// Before every step, if a point is too far from the trust region,
+/// it's replaced by a point of the trust region improving the geometry.
+/// The model is minimized with the dogleg method.
+///
+/// Only the function values are used: the gradient and Hessian seen by
+/// the trust region iteration are the ones of the model.
+///
+/// References:
+///
+/// Andrew R. Conn, Katya Scheinberg and Luís N. Vicente (2009).
+/// Introduction to Derivative-Free Optimization. [chapters 6 and 10]
+///
+/// M. J. D. Powell (2006). The NEWUOA software for unconstrained
+/// optimization without derivatives.
+#[derive(Serialize, Deserialize, Solverf64)]
+pub struct InterpolationTrustRegion {
+    delta: f64,
+    radius: f64,
+    points: Vec<(DVector<f64>, f64)>,
+    // index of the current iterate in points
+    center: usize,
+    grad: DVector<f64>,
+    hessian: DMatrix<f64>,
+}
+
+impl InterpolationTrustRegion {
+    /// The initial interpolation points are at distance radius
+    /// from the initial point.
+    pub fn new(radius: f64) -> Self {
+        InterpolationTrustRegion {
+            delta: radius,
+            radius,
+            points: Vec::new(),
+            center: 0,
+            grad: DVector::zeros(0),
+            hessian: DMatrix::zeros(0, 0),
+        }
+    }
+
+    fn build<O>(&mut self, op: &mut OpWrapper<O>, param: &DVector<f64>, cost: f64, radius: f64)
+        -> Result<(), Error>
+    where
+        O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+    {
+        let n = param.len();
+        self.points = vec![(param.clone(), cost)];
+        self.center = 0;
+
+        for i in 0 .. n {
+            for &sign in [1.0, -1.0].iter() {
+                let mut point = param.clone();
+                point[i] += sign * radius;
+                let value = op.apply(&point)?;
+                self.points.push((point, value));
+            }
+        }
+
+        for i in 0 .. n {
+            for j in i + 1 .. n {
+                let mut point = param.clone();
+                point[i] += radius;
+                point[j] += radius;
+                let value = op.apply(&point)?;
+                self.points.push((point, value));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn distance(&self, i: usize) -> f64 {
+        (&self.points[i].0 - &self.points[self.center].0).norm()
+    }
+
+    /// Largest distance from the current iterate, used for scaling.
+    fn scale(&self) -> f64 {
+        (0 .. self.points.len())
+            .map(|i| self.distance(i))
+            .fold(0.0, f64::max)
+    }
+
+    /// Monomials 1, s_i, ½ s_i², s_i s_j at the scaled displacement of param,
+    /// None if all the interpolation points have collapsed onto the iterate.
+    fn basis(&self, param: &DVector<f64>) -> Option<DVector<f64>> {
+        let scale = self.scale();
+        if scale == 0.0 {
+            return None;
+        }
+
+        let s = (param - &self.points[self.center].0) / scale;
+        let n = s.len();
+
+        let mut basis = vec![1.0];
+        basis.extend(s.iter());
+        basis.extend(s.iter().map(|s_i| 0.5 * s_i * s_i));
+        for i in 0 .. n {
+            for j in i + 1 .. n {
+                basis.push(s[i] * s[j]);
+            }
+        }
+
+        Some(DVector::from_vec(basis))
+    }
+
+    fn interpolation_matrix(&self) -> Option<DMatrix<f64>> {
+        let p = self.points.len();
+        let mut mat = DMatrix::zeros(p, p);
+        for (i, (point, _)) in self.points.iter().enumerate() {
+            mat.set_row(i, &self.basis(point)?.transpose());
+        }
+        Some(mat)
+    }
+
+    /// Values of all the Lagrange polynomials at param.
+    fn lagrange(&self, param: &DVector<f64>) -> Option<DVector<f64>> {
+        self.interpolation_matrix()?
+            .transpose()
+            .lu()
+            .solve(&self.basis(param)?)
+    }
+
+    /// Model gradient and Hessian at the current iterate,
+    /// None if the interpolation set is degenerate.
+    fn fit(&mut self) -> Option<()> {
+        let n = self.points[0].0.len();
+        let scale = self.scale();
+
+        let values = DVector::from_iterator(self.points.len(), self.points.iter().map(|p| p.1));
+        let coefficients = self.interpolation_matrix()?.lu().solve(&values)?;
+
+        self.grad = DVector::from_fn(n, |i, _| coefficients[1 + i] / scale);
+        self.hessian = DMatrix::zeros(n, n);
+
+        let mut k = 1 + 2 * n;
+        for i in 0 .. n {
+            self.hessian[(i, i)] = coefficients[1 + n + i] / scale.powi(2);
+            for j in i + 1 .. n {
+                self.hessian[(i, j)] = coefficients[k] / scale.powi(2);
+                self.hessian[(j, i)] = self.hessian[(i, j)];
+                k += 1;
+            }
+        }
+
+        Some(())
+    }
+
+    /// Fit the model, rebuilding the interpolation set around
+    /// the current iterate if it's degenerate.
+    fn refit<O>(&mut self, op: &mut OpWrapper<O>) -> Result<(), Error>
+    where
+        O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+    {
+        if self.fit().is_none() {
+            let (param, cost) = self.points[self.center].clone();
+            self.build(op, &param, cost, self.delta.max(MIN_RADIUS))?;
+            self.fit().ok_or(Error::msg("Degenerate interpolation set."))?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace the point farthest from the current iterate,
+    /// if it's outside twice the trust region, by the point
+    /// x ± delta e_j maximizing its Lagrange polynomial.
+    fn improve_geometry<O>(&mut self, op: &mut OpWrapper<O>) -> Result<(), Error>
+    where
+        O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+    {
+        let farthest = (0 .. self.points.len())
+            .max_by(|&i, &j| self.distance(i).partial_cmp(&self.distance(j)).unwrap())
+            .unwrap_or(self.center);
+
+        if self.distance(farthest) <= 2.0 * self.delta {
+            return Ok(());
+        }
+
+        let param = self.points[self.center].0.clone();
+        let mut best = None;
+        let mut best_value = 0.0;
+        for j in 0 .. param.len() {
+            for &sign in [1.0, -1.0].iter() {
+                let mut candidate = param.clone();
+                candidate[j] += sign * self.delta;
+
+                if let Some(lagrange) = self.lagrange(&candidate) {
+                    if lagrange[farthest].abs() > best_value {
+                        best_value = lagrange[farthest].abs();
+                        best = Some(candidate);
+                    }
+                }
+            }
+        }
+
+        if let Some(point) = best {
+            let value = op.apply(&point)?;
+            self.points[farthest] = (point, value);
+        }
+
+        Ok(())
+    }
+}
+
+impl<O> TrustRegion<O, f64> for InterpolationTrustRegion
+where
+    O: ArgminOp<
+        Output = f64,
+        Float = f64,
+        Param = DVector<f64>,
+        Hessian = DMatrix<f64>
+    >
+{
+    fn solve_subproblem(&mut self, op: &mut OpWrapper<O>, _state: &IterState<O>, delta: f64)
+        -> Result<O::Param, Error> {
+        self.improve_geometry(op)?;
+        self.refit(op)?;
+
+        dogleg_step(&self.grad, &self.hessian, || {
+            let (mat_l, vec_d) = cholesky::factorization(&self.hessian, CHOL_DELTA, CHOL_BETA)?;
+            cholesky::solve(&mat_l, &vec_d, &(-&self.grad))
+        }, delta)
+    }
+
+    fn subproblem(&self, state: &IterState<O>, param: &O::Param)
+        -> Result<f64, Error> {
+        let value = 0.5 * (&self.hessian * param).dot(param)
+            + self.grad.dot(param)
+            + state.cost;
+
+        Ok(value)
+    }
+
+    fn update_model(&mut self, op: &mut OpWrapper<O>, param: &O::Param, cost: f64)
+        -> Result<(), Error> {
+        if self.points.is_empty() {
+            return self.build(op, param, cost, self.radius);
+        }
+
+        // Replacing the point with the largest weighted Lagrange polynomial
+        // keeps the interpolation matrix as far as possible from singular.
+        let lagrange = self.lagrange(param)
+            .unwrap_or(DVector::from_element(self.points.len(), 1.0));
+
+        let replaced = (0 .. self.points.len())
+            .filter(|&i| i != self.center)
+            .map(|i| (i, lagrange[i].abs() * (self.distance(i) / self.delta).powi(2).max(1.0)))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(i, _)| i)
+            .ok_or(Error::msg("Empty interpolation set."))?;
+        self.points[replaced] = (param.clone(), cost);
+
+        Ok(())
+    }
+
+    fn model_gradient(&mut self, op: &mut OpWrapper<O>, param: &O::Param)
+        -> Result<O::Param, Error> {
+        self.center = self.points
+            .iter()
+            .position(|(point, _)| point == param)
+            .ok_or(Error::msg("The iterate is not an interpolation point."))?;
+        self.refit(op)?;
+
+        // criticality step: rebuild the interpolation set until its size
+        // is comparable with the model gradient.
+        loop {
+            let grad_norm = self.grad.norm();
+            let radius = grad_norm.max(MIN_RADIUS);
+            if grad_norm > CRITICALITY || self.scale() <= 2.0 * radius {
+                break;
+            }
+
+            let (param, cost) = self.points[self.center].clone();
+            self.delta = self.delta.min(radius);
+            self.build(op, &param, cost, radius)?;
+            self.refit(op)?;
+        }
+
+        Ok(self.grad.clone())
+    }
+
+    fn model_hessian(&mut self, _op: &mut OpWrapper<O>, _param: &O::Param)
+        -> Result<O::Hessian, Error> {
+        Ok(self.hessian.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::rosenbrock::Rosenbrock2D;
+
+    /// Rosenbrock's function without derivatives.
+    #[derive(Clone)]
+    struct BlackBox {
+        func: Rosenbrock2D
+    }
+
+    impl ArgminOp for BlackBox {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = DMatrix<f64>;
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            self.func.apply(param)
+        }
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let func = BlackBox { func: Rosenbrock2D::new(1.0, 100.0) };
+        let x0 = DVector::from_row_slice(&[-1.2, 1.0]);
+
+        let res = Executor::new(func, InterpolationTrustRegion::new(0.1), x0)
+            .max_iters(1000)
+            .run()
+            .unwrap();
+
+        assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+        assert!((&res.state.param - DVector::from_row_slice(&[1.0, 1.0])).norm() < 1E-4);
+    }
+
+    #[test]
+    fn test_collapsed_points() {
+        let param = DVector::from_row_slice(&[-1.2, 1.0]);
+        let mut solver = InterpolationTrustRegion::new(0.1);
+        solver.points = vec![(param.clone(), 24.2); 6];
+
+        // degenerate, so that refit rebuilds the interpolation set
+        assert!(solver.basis(&param).is_none());
+        assert!(solver.fit().is_none());
+    }
+}
@@ -0,0 +1,9 @@
+mod cmaes;
+mod interpolation;
+mod neldermead;
+mod patternsearch;
+
+pub use cmaes::*;
+pub use interpolation::*;
+pub use neldermead::*;
+pub use patternsearch::*;
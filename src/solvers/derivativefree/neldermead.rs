@@ -0,0 +1,275 @@
+use argmin::prelude::*;
+use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
+
+static TOLERANCE_X : f64 = 1E-8;
+static TOLERANCE_F : f64 = 1E-10;
+
+/// Nelder-Mead simplex method, with the dimension dependent coefficients
+/// of Gao and Han
+/// reflection 1, expansion 1 + 2/n,
+/// contraction 3/4 - 1/2n, shrinkage 1 - 1/n.
+///
+/// Only the function values are used, neither the gradient
+/// nor the Hessian are ever evaluated.
+/// When the simplex has collapsed, it is rebuilt around the best vertex,
+/// until a restart doesn't improve the best value any more.
+///
+/// Reference:
+///
+/// Fuchang Gao and Lixing Han (2012). Implementing the Nelder-Mead simplex
+/// algorithm with adaptive parameters.
+#[derive(Serialize, Deserialize)]
+pub struct NelderMead {
+    initial_size: f64,
+    tolerance_x: f64,
+    tolerance_f: f64,
+    max_restarts: u32,
+    restarts: u32,
+    restart_value: f64,
+    converged: bool,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    delta: f64,
+    // vertices sorted by function value
+    simplex: Vec<(DVector<f64>, f64)>,
+}
+
+impl NelderMead {
+    /// The initial simplex spans initial_size along every coordinate.
+    pub fn new(initial_size: f64) -> Self {
+        NelderMead {
+            initial_size,
+            tolerance_x: TOLERANCE_X,
+            tolerance_f: TOLERANCE_F,
+            max_restarts: 0,
+            restarts: 0,
+            restart_value: f64::INFINITY,
+            converged: false,
+            alpha: 1.0,
+            beta: 2.0,
+            gamma: 0.5,
+            delta: 0.5,
+            simplex: Vec::new(),
+        }
+    }
+
+    /// Terminate when the simplex is smaller than tolerance_x
+    /// and the function values differ less than tolerance_f.
+    pub fn with_tolerances(mut self, tolerance_x: f64, tolerance_f: f64) -> Self {
+        self.tolerance_x = tolerance_x;
+        self.tolerance_f = tolerance_f;
+        self
+    }
+
+    pub fn with_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    fn build_simplex<O>(&mut self, op: &mut OpWrapper<O>, vertex: &DVector<f64>, value: f64)
+        -> Result<(), Error>
+    where
+        O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+    {
+        self.simplex = vec![(vertex.clone(), value)];
+        for i in 0 .. vertex.len() {
+            let mut next = vertex.clone();
+            next[i] += self.initial_size;
+            let next_value = op.apply(&next)?;
+            self.simplex.push((next, next_value));
+        }
+        self.sort();
+
+        Ok(())
+    }
+
+    fn sort(&mut self) {
+        self.simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    fn has_converged(&self) -> bool {
+        let (best, best_value) = &self.simplex[0];
+        let (_, worst_value) = &self.simplex[self.simplex.len() - 1];
+
+        let size = self.simplex[1 ..]
+            .iter()
+            .map(|(vertex, _)| (vertex - best).norm())
+            .fold(0.0, f64::max);
+
+        size <= self.tolerance_x && worst_value - best_value <= self.tolerance_f
+    }
+}
+
+impl<O> Solver<O> for NelderMead
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    const NAME: &'static str = "NelderMead";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        if param.is_empty() {
+            return Err(Error::msg("Nelder-Mead needs at least one variable."));
+        }
+        let n = param.len() as f64;
+
+        self.alpha = 1.0;
+        self.beta = 1.0 + 2.0 / n;
+        self.gamma = 0.75 - 1.0 / (2.0 * n);
+        self.delta = 1.0 - 1.0 / n;
+
+        let value = op.apply(&param)?;
+        self.build_simplex(op, &param, value)?;
+
+        let (best, best_value) = self.simplex[0].clone();
+        Ok(Some(ArgminIterData::new().param(best).cost(best_value)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let n = self.simplex.len() - 1;
+        let centroid = self.simplex[.. n]
+            .iter()
+            .fold(DVector::zeros(self.simplex[0].0.len()), |acc, (vertex, _)| acc + vertex)
+            / n as f64;
+
+        let (worst, worst_value) = self.simplex[n].clone();
+        let best_value = self.simplex[0].1;
+        let second_worst_value = self.simplex[n - 1].1;
+
+        let reflected = &centroid + self.alpha * (&centroid - &worst);
+        let reflected_value = op.apply(&reflected)?;
+
+        let mut shrink = false;
+        if reflected_value < best_value {
+            let expanded = &centroid + self.beta * (&reflected - &centroid);
+            let expanded_value = op.apply(&expanded)?;
+
+            self.simplex[n] = if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            }
+            else {
+                (reflected, reflected_value)
+            };
+        }
+        else if reflected_value < second_worst_value {
+            self.simplex[n] = (reflected, reflected_value);
+        }
+        else if reflected_value < worst_value {
+            let contracted = &centroid + self.gamma * (&reflected - &centroid);
+            let contracted_value = op.apply(&contracted)?;
+
+            if contracted_value <= reflected_value {
+                self.simplex[n] = (contracted, contracted_value);
+            }
+            else {
+                shrink = true;
+            }
+        }
+        else {
+            let contracted = &centroid + self.gamma * (&worst - &centroid);
+            let contracted_value = op.apply(&contracted)?;
+
+            if contracted_value < worst_value {
+                self.simplex[n] = (contracted, contracted_value);
+            }
+            else {
+                shrink = true;
+            }
+        }
+
+        if shrink {
+            let best = self.simplex[0].0.clone();
+            for i in 1 ..= n {
+                let vertex = &best + self.delta * (&self.simplex[i].0 - &best);
+                let value = op.apply(&vertex)?;
+                self.simplex[i] = (vertex, value);
+            }
+        }
+        self.sort();
+
+        if self.has_converged() {
+            let (best, best_value) = self.simplex[0].clone();
+
+            if self.restarts < self.max_restarts && best_value < self.restart_value {
+                self.restarts += 1;
+                self.restart_value = best_value;
+                self.build_simplex(op, &best, best_value)?;
+            }
+            else {
+                self.converged = true;
+            }
+        }
+
+        let (best, best_value) = self.simplex[0].clone();
+        Ok(ArgminIterData::new().param(best).cost(best_value))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason
+    {
+        if self.converged {
+            TerminationReason::TargetToleranceReached
+        }
+        else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+    use crate::functions::Function;
+    use crate::functions::rosenbrock::Rosenbrock2D;
+
+    /// Rosenbrock's function without derivatives.
+    #[derive(Clone)]
+    struct BlackBox {
+        func: Rosenbrock2D
+    }
+
+    impl ArgminOp for BlackBox {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = DMatrix<f64>;
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            self.func.apply(param)
+        }
+    }
+
+    impl Function for BlackBox {}
+
+    #[test]
+    fn test_rosenbrock() {
+        let func = BlackBox { func: Rosenbrock2D::new(1.0, 100.0) };
+        let x0 = DVector::from_row_slice(&[-1.2, 1.0]);
+
+        let res = func.minimize(NelderMead::new(1.0).with_restarts(2), x0, 1000).unwrap();
+
+        assert_eq!(TerminationReason::TargetToleranceReached, res.state.termination_reason);
+        assert!((&res.state.best_param - DVector::from_row_slice(&[1.0, 1.0])).norm() < 1E-6);
+    }
+
+    #[test]
+    fn test_empty() {
+        let func = BlackBox { func: Rosenbrock2D::new(1.0, 100.0) };
+
+        assert!(Executor::new(func, NelderMead::new(1.0), DVector::zeros(0))
+            .max_iters(10)
+            .run()
+            .is_err());
+    }
+}
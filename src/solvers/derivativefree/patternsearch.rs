@@ -0,0 +1,237 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+static TOLERANCE : f64 = 1E-8;
+static EXPANSION : f64 = 2.0;
+static CONTRACTION : f64 = 0.5;
+
+/// Poll directions, positive spanning sets of the whole space.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Pattern {
+    /// ±e_i, 2n directions.
+    Compass,
+    /// e_1, ..., e_n and -(e_1 + ... + e_n), the minimal positive basis
+    /// with n + 1 directions.
+    MinimalBasis,
+}
+
+impl Pattern {
+    /// Directions as columns of a matrix. They have integer entries,
+    /// so that all the trial points lie on the mesh.
+    fn directions(&self, n: usize) -> DMatrix<f64> {
+        match *self {
+            Pattern::Compass => {
+                let identity = DMatrix::<f64>::identity(n, n);
+                let mut directions = DMatrix::zeros(n, 2 * n);
+                directions.columns_mut(0, n).copy_from(&identity);
+                directions.columns_mut(n, n).copy_from(&(-identity));
+                directions
+            },
+            Pattern::MinimalBasis => {
+                let mut directions = DMatrix::from_element(n, n + 1, -1.0);
+                directions.columns_mut(0, n).fill_with_identity();
+                directions
+            },
+        }
+    }
+}
+
+/// Generalized pattern search.
+///
+/// Every iteration polls the points x + delta d around the current point,
+/// for the directions d of the pattern, and moves to the first one
+/// improving the function value. The mesh size delta is expanded after
+/// a successful poll and contracted after an unsuccessful one, the solver
+/// terminates when it's smaller than the tolerance.
+/// The directions are polled starting from the last successful one.
+///
+/// The optional search step is the pattern move of Hooke and Jeeves:
+/// after an iteration which moved the current point, it tries
+/// x_k + (x_k - x_k-1) before polling. Since the mesh size only changes
+/// by powers of 2, the pattern points lie on the finest mesh so far.
+///
+/// Only the function values are used, and only their order matters,
+/// which makes the method robust to noise and scaling of the objective.
+///
+/// Reference:
+///
+/// Tamara G. Kolda, Robert Michael Lewis and Virginia Torczon (2003).
+/// Optimization by Direct Search: New Perspectives on Some Classical
+/// and Modern Methods.
+#[derive(Serialize, Deserialize)]
+pub struct PatternSearch {
+    pattern: Pattern,
+    delta: f64,
+    tolerance: f64,
+    search: bool,
+    directions: DMatrix<f64>,
+    // indices of the directions, in polling order
+    order: Vec<usize>,
+    // x_k - x_k-1, if the last iteration moved the current point
+    last_step: Option<DVector<f64>>,
+}
+
+impl PatternSearch {
+    /// The initial mesh size is initial_size.
+    pub fn new(pattern: Pattern, initial_size: f64) -> Self {
+        PatternSearch {
+            pattern,
+            delta: initial_size,
+            tolerance: TOLERANCE,
+            search: false,
+            directions: DMatrix::zeros(0, 0),
+            order: Vec::new(),
+            last_step: None,
+        }
+    }
+
+    /// Terminate when the mesh size is smaller than tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Enable the search step.
+    pub fn with_search(mut self) -> Self {
+        self.search = true;
+        self
+    }
+
+    /// First improving trial point of the poll step, if any.
+    fn poll<O>(&mut self, op: &mut OpWrapper<O>, param: &DVector<f64>, value: f64)
+        -> Result<Option<(DVector<f64>, f64)>, Error>
+    where
+        O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+    {
+        for k in 0 .. self.order.len() {
+            let direction = self.order[k];
+            let trial = param + self.delta * self.directions.column(direction);
+            let trial_value = op.apply(&trial)?;
+
+            if trial_value < value {
+                self.order.remove(k);
+                self.order.insert(0, direction);
+                return Ok(Some((trial, trial_value)));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl<O> Solver<O> for PatternSearch
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    const NAME: &'static str = "PatternSearch";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+
+        self.directions = self.pattern.directions(param.len());
+        self.order = (0 .. self.directions.ncols()).collect();
+        self.last_step = None;
+
+        let value = op.apply(&param)?;
+        Ok(Some(ArgminIterData::new().param(param).cost(value)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = &state.param;
+        let value = state.cost;
+
+        if self.search {
+            if let Some(step) = &self.last_step {
+                let trial = param + step;
+                let trial_value = op.apply(&trial)?;
+
+                // x_k+1 - x_k = x_k - x_k-1, so the same move is tried next
+                if trial_value < value {
+                    return Ok(ArgminIterData::new().param(trial).cost(trial_value));
+                }
+            }
+        }
+
+        match self.poll(op, param, value)? {
+            Some((trial, trial_value)) => {
+                self.last_step = Some(&trial - param);
+                self.delta *= EXPANSION;
+
+                Ok(ArgminIterData::new().param(trial).cost(trial_value))
+            },
+            None => {
+                self.last_step = None;
+                self.delta *= CONTRACTION;
+
+                Ok(ArgminIterData::new().param(param.clone()).cost(value))
+            },
+        }
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason
+    {
+        if self.delta <= self.tolerance {
+            TerminationReason::TargetToleranceReached
+        }
+        else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::rosenbrock::Rosenbrock2D;
+
+    /// Rosenbrock's function without derivatives.
+    #[derive(Clone)]
+    struct BlackBox {
+        func: Rosenbrock2D
+    }
+
+    impl ArgminOp for BlackBox {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            self.func.apply(param)
+        }
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let solvers = vec![
+            PatternSearch::new(Pattern::Compass, 1.0),
+            PatternSearch::new(Pattern::MinimalBasis, 1.0),
+            PatternSearch::new(Pattern::Compass, 1.0).with_search(),
+        ];
+
+        for solver in solvers {
+            let func = BlackBox { func: Rosenbrock2D::new(1.0, 100.0) };
+            let x0 = DVector::from_row_slice(&[-1.2, 1.0]);
+
+            // the coordinate directions follow the curved valley
+            // with small steps, which takes several thousand iterations
+            let res = Executor::new(func, solver, x0)
+                .max_iters(10000)
+                .run()
+                .unwrap();
+
+            assert_eq!(TerminationReason::TargetToleranceReached, res.state.termination_reason);
+            assert!((&res.state.best_param - DVector::from_row_slice(&[1.0, 1.0])).norm() < 1E-5);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::functions::system::Merit;
+use crate::steplength::backtracking;
+
+/// Ratio ‖F(x_k+1)‖ / ‖F(x_k)‖ above which the iteration is considered stalled.
+static STALL_RATIO : f64 = 0.9;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum BroydenUpdate {
+    /// Rank-one update of the Jacobian approximation B,
+    /// B+ = B + (y - B s) sᵀ / sᵀs.
+    Good,
+    /// Rank-one update of the inverse Jacobian approximation H,
+    /// H+ = H + (s - H y) yᵀ / yᵀy.
+    Bad,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum InitialJacobian {
+    Identity,
+    /// Forward differences of the residual, one evaluation per column.
+    FiniteDifference,
+    /// The Jacobian provided by the system.
+    Exact,
+}
+
+/// Broyden's quasi-Newton method for square nonlinear systems F(x) = 0,
+/// globalized with a backtracking line search on the merit function
+/// ½‖F(x)‖².
+///
+/// The state gradient is the gradient of the merit function
+/// of the linear model, i.e. Bᵀ F. Along the quasi-Newton step its slope
+/// is always -‖F‖², so the line search uses the slope Fᵀ J d of the true
+/// merit function instead, approximated by a forward difference
+/// of the residual at the cost of one more evaluation per iteration.
+///
+/// Reference:
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// [chapter 11.1]
+#[derive(Serialize, Deserialize)]
+pub struct Broyden {
+    update: BroydenUpdate,
+    initial: InitialJacobian,
+    restart: bool,
+    tolerance: f64,
+    residual: DVector<f64>,
+    approximation: DMatrix<f64>,
+}
+
+impl Broyden {
+    /// The solver terminates when ‖F(x)‖ <= tolerance.
+    pub fn new(update: BroydenUpdate, initial: InitialJacobian, tolerance: f64) -> Self {
+        Broyden {
+            update,
+            initial,
+            restart: false,
+            tolerance,
+            residual: DVector::zeros(0),
+            approximation: DMatrix::zeros(0, 0),
+        }
+    }
+
+    /// Re-initializes the Jacobian approximation, using the initial
+    /// strategy, whenever the step isn't a descent direction
+    /// of the merit function or the residual doesn't decrease enough.
+    /// Without restarts, or if the step of the re-initialized approximation
+    /// isn't a descent direction either, such a step is an error.
+    pub fn restart_on_stall(mut self) -> Self {
+        self.restart = true;
+        self
+    }
+
+    fn initial_approximation<O>(
+        &self,
+        op: &mut OpWrapper<O>,
+        param: &DVector<f64>,
+        residual: &DVector<f64>
+    ) -> Result<DMatrix<f64>, Error>
+    where
+        O: ArgminOp<
+            Param = DVector<f64>,
+            Output = DVector<f64>,
+            Hessian = (),
+            Jacobian = DMatrix<f64>,
+            Float = f64
+        >
+    {
+        let jacobian = match self.initial {
+            InitialJacobian::Identity => return Ok(DMatrix::identity(param.len(), param.len())),
+            InitialJacobian::FiniteDifference => finite_difference_jacobian(op, param, residual)?,
+            InitialJacobian::Exact => op.jacobian(param)?,
+        };
+
+        match self.update {
+            BroydenUpdate::Good => Ok(jacobian),
+            BroydenUpdate::Bad => jacobian
+                .try_inverse()
+                .ok_or(Error::msg("Unable to initialize Broyden's method: the Jacobian is singular.")),
+        }
+    }
+
+    fn direction(&self) -> Result<DVector<f64>, Error> {
+        match self.update {
+            BroydenUpdate::Good => self.approximation.clone()
+                .lu()
+                .solve(&(-&self.residual))
+                .ok_or(Error::msg("Unable to compute the quasi-Newton step: the Jacobian approximation is singular.")),
+            BroydenUpdate::Bad => Ok(-(&self.approximation * &self.residual)),
+        }
+    }
+
+    /// Gradient Bᵀ F of the merit function of the linear model.
+    fn model_gradient(&self, residual: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        match self.update {
+            BroydenUpdate::Good => Ok(self.approximation.transpose() * residual),
+            BroydenUpdate::Bad => self.approximation.transpose()
+                .lu()
+                .solve(residual)
+                .ok_or(Error::msg("Unable to compute the quasi-Newton step: the Jacobian approximation is singular.")),
+        }
+    }
+
+    fn update_approximation(&mut self, s: &DVector<f64>, y: &DVector<f64>) {
+        match self.update {
+            BroydenUpdate::Good => {
+                let s_norm = s.norm_squared();
+                if s_norm > 0.0 {
+                    let correction = (y - &self.approximation * s) / s_norm;
+                    self.approximation += correction * s.transpose();
+                }
+            },
+            BroydenUpdate::Bad => {
+                let y_norm = y.norm_squared();
+                if y_norm > 0.0 {
+                    let correction = (s - &self.approximation * y) / y_norm;
+                    self.approximation += correction * y.transpose();
+                }
+            },
+        }
+    }
+}
+
+impl<O> Solver<O> for Broyden
+where
+    O: ArgminOp<
+        Param = DVector<f64>,
+        Output = DVector<f64>,
+        Hessian = (),
+        Jacobian = DMatrix<f64>,
+        Float = f64
+    >
+{
+    const NAME: &'static str = "Broyden";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        let residual = op.apply(&param)?;
+        self.approximation = self.initial_approximation(op, &param, &residual)?;
+
+        let iter_data = ArgminIterData::<O>::new()
+            .cost(0.5 * residual.norm_squared())
+            .grad(self.model_gradient(&residual)?)
+            .param(param);
+        self.residual = residual;
+
+        Ok(Some(iter_data))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+
+        let mut descent_dir = self.direction()?;
+        let mut slope = merit_slope(op, &param, &self.residual, &descent_dir)?;
+        if self.restart && slope >= 0.0 {
+            self.approximation = self.initial_approximation(op, &param, &self.residual)?;
+            descent_dir = self.direction()?;
+            slope = merit_slope(op, &param, &self.residual, &descent_dir)?;
+        }
+        if slope >= 0.0 {
+            return Err(Error::msg("The quasi-Newton step is not a descent direction of the merit function."));
+        }
+
+        let merit = Merit::new(&*op);
+        let step_length = backtracking::search_with_slope(
+            &merit, &param, state.cost, slope, &descent_dir, 1.0
+        )?;
+
+        let step = step_length * descent_dir;
+        let next_param = &param + &step;
+        let next_residual = op.apply(&next_param)?;
+
+        if self.restart && next_residual.norm() > STALL_RATIO * self.residual.norm() {
+            self.approximation = self.initial_approximation(op, &next_param, &next_residual)?;
+        }
+        else {
+            self.update_approximation(&step, &(&next_residual - &self.residual));
+        }
+
+        let iter_data = ArgminIterData::new()
+            .cost(0.5 * next_residual.norm_squared())
+            .grad(self.model_gradient(&next_residual)?)
+            .param(next_param);
+        self.residual = next_residual;
+
+        Ok(iter_data)
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason
+    {
+        if self.residual.norm() <= self.tolerance {
+            TerminationReason::TargetPrecisionReached
+        }
+        else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+/// Forward difference approximation of the slope Fᵀ J d
+/// of the merit function ½‖F‖² along d, given the residual F(param).
+fn merit_slope<O>(
+    op: &mut OpWrapper<O>,
+    param: &DVector<f64>,
+    residual: &DVector<f64>,
+    dir: &DVector<f64>
+) -> Result<f64, Error>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = DVector<f64>>
+{
+    let dir_norm = dir.norm();
+    if dir_norm == 0.0 {
+        return Ok(0.0);
+    }
+
+    let h = f64::EPSILON.sqrt() * param.norm().max(1.0) / dir_norm;
+    let shifted = op.apply(&(param + h * dir))?;
+
+    Ok(residual.dot(&(shifted - residual)) / h)
+}
+
+/// Forward difference approximation of the Jacobian at param,
+/// given the residual F(param).
+fn finite_difference_jacobian<O>(
+    op: &mut OpWrapper<O>,
+    param: &DVector<f64>,
+    residual: &DVector<f64>
+) -> Result<DMatrix<f64>, Error>
+where
+    O: ArgminOp<Param = DVector<f64>, Output = DVector<f64>>
+{
+    let mut jacobian = DMatrix::zeros(residual.len(), param.len());
+
+    for j in 0 .. param.len() {
+        let h = f64::EPSILON.sqrt() * param[j].abs().max(1.0);
+        let mut shifted = param.clone();
+        shifted[j] += h;
+
+        let column = (op.apply(&shifted)? - residual) / h;
+        jacobian.set_column(j, &column);
+    }
+
+    Ok(jacobian)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::system::System;
+
+    /// Broyden's tridiagonal function
+    /// F_i = (3 - 2 x_i) x_i - x_i-1 - 2 x_i+1 + 1,
+    /// with x_0 = x_n+1 = 0.
+    #[derive(Clone)]
+    struct Tridiagonal {}
+
+    impl ArgminOp for Tridiagonal {
+        type Param = DVector<f64>;
+        type Output = DVector<f64>;
+        type Hessian = ();
+        type Jacobian = DMatrix<f64>;
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            let n = param.len();
+            Ok(DVector::from_fn(n, |i, _| {
+                let prev = if i > 0 { param[i - 1] } else { 0.0 };
+                let next = if i + 1 < n { param[i + 1] } else { 0.0 };
+                (3.0 - 2.0 * param[i]) * param[i] - prev - 2.0 * next + 1.0
+            }))
+        }
+    }
+
+    impl System for Tridiagonal {}
+
+    #[test]
+    fn test_broyden() {
+        let x0 = DVector::from_element(10, -1.0);
+
+        for &update in [BroydenUpdate::Good, BroydenUpdate::Bad].iter() {
+            let solver = Broyden::new(update, InitialJacobian::FiniteDifference, 1E-8)
+                .restart_on_stall();
+            let res = Tridiagonal {}.solve(solver, x0.clone()).unwrap();
+
+            let residual = Tridiagonal {}.apply(&res.state.param).unwrap();
+            assert!(residual.norm() <= 1E-8);
+        }
+    }
+
+    #[test]
+    fn test_uphill() {
+        // the Jacobian is negative definite at x0, so -F is uphill
+        let x0 = DVector::from_element(10, 2.0);
+        let solver = Broyden::new(BroydenUpdate::Good, InitialJacobian::Identity, 1E-8);
+
+        assert!(Executor::new(Tridiagonal {}, solver, x0).max_iters(10).run().is_err());
+    }
+}
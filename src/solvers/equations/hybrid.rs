@@ -0,0 +1,243 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::solvers::newton::{cholesky, dogleg_step};
+
+static CHOL_DELTA : f64 = 1E-4;
+static CHOL_BETA : f64 = 100.0;
+
+/// Initial trust region radius, relative to the scaled initial point.
+static FACTOR : f64 = 100.0;
+
+/// Minimum ratio between actual and predicted reduction
+/// for accepting a step.
+static ETA : f64 = 1E-4;
+
+/// Powell's hybrid method for square nonlinear systems F(x) = 0.
+///
+/// Every iteration minimizes the linearized residual ½‖F + J p‖²
+/// with a dogleg step, inside the trust region ‖D p‖ <= delta.
+/// As in MINPACK, the scaling D holds the largest column norms of
+/// the Jacobians seen so far, which makes the method invariant
+/// to the scaling of the variables.
+/// Unlike MINPACK, the Jacobian is evaluated at every accepted point
+/// instead of being updated with Broyden's formula.
+///
+/// Reference:
+///
+/// Jorge J. Moré, Burton S. Garbow and Kenneth E. Hillstrom (1980).
+/// User Guide for MINPACK-1. [hybrj]
+#[derive(Serialize, Deserialize)]
+pub struct PowellHybrid {
+    tolerance: f64,
+    delta: f64,
+    scaling: DVector<f64>,
+    residual: DVector<f64>,
+}
+
+impl PowellHybrid {
+    /// The solver terminates when ‖F(x)‖ <= tolerance.
+    pub fn new(tolerance: f64) -> Self {
+        PowellHybrid {
+            tolerance,
+            delta: 0.0,
+            scaling: DVector::zeros(0),
+            residual: DVector::zeros(0),
+        }
+    }
+
+    fn update_scaling(&mut self, jacobian: &DMatrix<f64>) {
+        for (j, column) in jacobian.column_iter().enumerate() {
+            self.scaling[j] = self.scaling[j].max(column.norm());
+        }
+    }
+
+    /// Dogleg step for the linearized residual, in unscaled variables.
+    fn solve_subproblem(&self, jacobian: &DMatrix<f64>) -> Result<DVector<f64>, Error> {
+        let scaled_jacobian = DMatrix::from_fn(
+            jacobian.nrows(),
+            jacobian.ncols(),
+            |i, j| jacobian[(i, j)] / self.scaling[j]
+        );
+
+        let grad = scaled_jacobian.transpose() * &self.residual;
+        let hessian = scaled_jacobian.transpose() * &scaled_jacobian;
+
+        // The Newton step solves the linearized system exactly,
+        // when the Jacobian is singular fall back to a regularized
+        // Gauss-Newton step.
+        let full_step = || match scaled_jacobian.clone().lu().solve(&(-&self.residual)) {
+            Some(newton) => Ok(newton),
+            None => {
+                let (mat_l, vec_d) = cholesky::factorization(&hessian, CHOL_DELTA, CHOL_BETA)?;
+                cholesky::solve(&mat_l, &vec_d, &(-&grad))
+            }
+        };
+
+        let scaled_step = dogleg_step(&grad, &hessian, full_step, self.delta)?;
+        Ok(scaled_step.component_div(&self.scaling))
+    }
+}
+
+impl<O> Solver<O> for PowellHybrid
+where
+    O: ArgminOp<
+        Param = DVector<f64>,
+        Output = DVector<f64>,
+        Hessian = (),
+        Jacobian = DMatrix<f64>,
+        Float = f64
+    >
+{
+    const NAME: &'static str = "PowellHybrid";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        let residual = op.apply(&param)?;
+        let jacobian = op.jacobian(&param)?;
+
+        // Columns which are zero at the initial point get unit scaling.
+        self.scaling = DVector::from_iterator(
+            jacobian.ncols(),
+            jacobian.column_iter().map(|column| {
+                let norm = column.norm();
+                if norm > 0.0 { norm } else { 1.0 }
+            })
+        );
+
+        let scaled_norm = param.component_mul(&self.scaling).norm();
+        self.delta = if scaled_norm > 0.0 { FACTOR * scaled_norm } else { FACTOR };
+
+        let iter_data = ArgminIterData::<O>::new()
+            .cost(0.5 * residual.norm_squared())
+            .grad(jacobian.transpose() * &residual)
+            .param(param)
+            .jacobian(jacobian);
+        self.residual = residual;
+
+        Ok(Some(iter_data))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let jacobian = state.jacobian
+            .as_ref()
+            .ok_or(Error::msg("jacobian unavailable"))?;
+
+        let step = self.solve_subproblem(jacobian)?;
+        let scaled_step_norm = step.component_mul(&self.scaling).norm();
+
+        let next_param = &state.param + &step;
+        let next_residual = op.apply(&next_param)?;
+
+        let predicted = self.residual.norm_squared() - (&self.residual + jacobian * &step).norm_squared();
+        let actual = self.residual.norm_squared() - next_residual.norm_squared();
+        let rho = if predicted > 0.0 { actual / predicted } else { -1.0 };
+
+        // update the trust region radius
+        if rho < 0.1 {
+            self.delta = 0.5 * self.delta.min(scaled_step_norm);
+        }
+        else if rho >= 0.5 {
+            self.delta = self.delta.max(2.0 * scaled_step_norm);
+        }
+
+        if rho <= ETA {
+            let grad = state.grad
+                .clone()
+                .ok_or(Error::msg("gradient unavailable"))?;
+
+            return Ok(ArgminIterData::new()
+                .param(state.param.clone())
+                .cost(state.cost)
+                .grad(grad)
+                .jacobian(jacobian.clone()));
+        }
+
+        let next_jacobian = op.jacobian(&next_param)?;
+        self.update_scaling(&next_jacobian);
+
+        let iter_data = ArgminIterData::new()
+            .cost(0.5 * next_residual.norm_squared())
+            .grad(next_jacobian.transpose() * &next_residual)
+            .param(next_param)
+            .jacobian(next_jacobian);
+        self.residual = next_residual;
+
+        Ok(iter_data)
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason
+    {
+        if self.residual.norm() <= self.tolerance {
+            TerminationReason::TargetPrecisionReached
+        }
+        else if self.delta <= f64::EPSILON * self.scaling.norm() {
+            TerminationReason::Aborted
+        }
+        else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::powell::PowellBadlyScaled;
+    use crate::functions::system::System;
+
+    #[test]
+    fn test_powell_badly_scaled() {
+        let x0 = DVector::from_row_slice(&[0.0, 1.0]);
+        let res = PowellBadlyScaled::new()
+            .solve(PowellHybrid::new(1E-10), x0)
+            .unwrap();
+
+        assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+
+        let expected = DVector::from_row_slice(&[1.098159329E-5, 9.106146740]);
+        assert!(((&res.state.param - &expected).component_div(&expected)).norm() < 1E-6);
+    }
+
+    /// F(x) = x² + 1, with no root and a stationary point
+    /// of ½‖F‖² at the origin.
+    #[derive(Clone)]
+    struct NoRoot {}
+
+    impl ArgminOp for NoRoot {
+        type Param = DVector<f64>;
+        type Output = DVector<f64>;
+        type Hessian = ();
+        type Jacobian = DMatrix<f64>;
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param.map(|x| x * x + 1.0))
+        }
+
+        fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(DMatrix::from_diagonal(&param.map(|x| 2.0 * x)))
+        }
+    }
+
+    #[test]
+    fn test_stationary_point() {
+        let res = Executor::new(NoRoot {}, PowellHybrid::new(1E-10), DVector::zeros(1))
+            .max_iters(10)
+            .run()
+            .unwrap();
+
+        // JᵀF = 0, so the dogleg step is zero and the radius collapses
+        assert_eq!(TerminationReason::Aborted, res.state.termination_reason);
+        assert_eq!(DVector::zeros(1), res.state.param);
+    }
+}
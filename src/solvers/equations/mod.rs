@@ -0,0 +1,7 @@
+mod broyden;
+mod hybrid;
+mod newton;
+
+pub use broyden::*;
+pub use hybrid::*;
+pub use newton::*;
@@ -0,0 +1,171 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::functions::system::Merit;
+use crate::steplength::backtracking;
+
+/// Newton's method for square nonlinear systems F(x) = 0,
+/// globalized with a backtracking line search on the merit function
+/// ½‖F(x)‖².
+///
+/// The state cost is the merit function, the state gradient its
+/// gradient Jᵀ F.
+///
+/// Reference:
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// [chapter 11.2]
+#[derive(Serialize, Deserialize)]
+pub struct NewtonSystem {
+    tolerance: f64,
+    residual: DVector<f64>,
+}
+
+impl NewtonSystem {
+    /// The solver terminates when ‖F(x)‖ <= tolerance.
+    pub fn new(tolerance: f64) -> Self {
+        NewtonSystem {
+            tolerance,
+            residual: DVector::zeros(0),
+        }
+    }
+}
+
+impl<O> Solver<O> for NewtonSystem
+where
+    O: ArgminOp<
+        Param = DVector<f64>,
+        Output = DVector<f64>,
+        Hessian = (),
+        Jacobian = DMatrix<f64>,
+        Float = f64
+    >
+{
+    const NAME: &'static str = "NewtonSystem";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        let residual = op.apply(&param)?;
+        let jacobian = op.jacobian(&param)?;
+
+        let iter_data = ArgminIterData::<O>::new()
+            .param(param)
+            .cost(0.5 * residual.norm_squared())
+            .grad(jacobian.transpose() * &residual)
+            .jacobian(jacobian);
+        self.residual = residual;
+
+        Ok(Some(iter_data))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = state.get_param();
+        let grad = state.grad
+            .as_ref()
+            .ok_or(Error::msg("gradient unavailable"))?;
+        let jacobian = state.jacobian
+            .as_ref()
+            .ok_or(Error::msg("jacobian unavailable"))?;
+
+        // Newton step J p = -F
+        let descent_dir = jacobian.clone()
+            .lu()
+            .solve(&(-&self.residual))
+            .filter(|p| p.iter().all(|v| v.is_finite()))
+            .ok_or(Error::msg("Unable to compute the Newton step: the Jacobian is singular."))?;
+
+        let merit = Merit::new(&*op);
+        let step_length = backtracking::search(
+            &merit, &param, state.cost, grad, &descent_dir, 1.0
+        )?;
+
+        let next_param = param + step_length * descent_dir;
+        let next_residual = op.apply(&next_param)?;
+        let next_jacobian = op.jacobian(&next_param)?;
+
+        let iter_data = ArgminIterData::new()
+            .cost(0.5 * next_residual.norm_squared())
+            .grad(next_jacobian.transpose() * &next_residual)
+            .param(next_param)
+            .jacobian(next_jacobian);
+        self.residual = next_residual;
+
+        Ok(iter_data)
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason
+    {
+        if self.residual.norm() <= self.tolerance {
+            TerminationReason::TargetPrecisionReached
+        }
+        else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::system::System;
+
+    /// x² + y² = 4, eˣ + y = 1
+    #[derive(Clone)]
+    struct Circle {}
+
+    impl ArgminOp for Circle {
+        type Param = DVector<f64>;
+        type Output = DVector<f64>;
+        type Hessian = ();
+        type Jacobian = DMatrix<f64>;
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            let (x, y) = (param[0], param[1]);
+            Ok(DVector::from_row_slice(&[
+                x.powi(2) + y.powi(2) - 4.0,
+                x.exp() + y - 1.0
+            ]))
+        }
+
+        fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+            let (x, y) = (param[0], param[1]);
+            Ok(DMatrix::from_row_slice(2, 2, &[
+                2.0 * x, 2.0 * y,
+                x.exp(), 1.0
+            ]))
+        }
+    }
+
+    impl System for Circle {}
+
+    #[test]
+    fn test_newton() {
+        let res = Circle {}
+            .solve(NewtonSystem::new(1E-10), DVector::from_row_slice(&[1.0, -1.5]))
+            .unwrap();
+
+        assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+
+        let residual = Circle {}.apply(&res.state.param).unwrap();
+        assert!(residual.norm() <= 1E-10);
+    }
+
+    #[test]
+    fn test_singular() {
+        // The Jacobian is singular for x = 0.
+        let res = Circle {}
+            .solve(NewtonSystem::new(1E-10), DVector::from_row_slice(&[0.0, 0.0]));
+
+        assert!(res.is_err());
+    }
+}
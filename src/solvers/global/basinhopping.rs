@@ -0,0 +1,308 @@
+use argmin::prelude::*;
+use nalgebra::DVector;
+use rand::prelude::*;
+
+use crate::functions::{converged, Function};
+use crate::functions::bounds::Bounds;
+
+static MAX_ITERS : u64 = 1000;
+
+/// Temperature at the k-th hop, from the initial temperature T_0.
+#[derive(Clone, Copy, Debug)]
+pub enum Schedule {
+    /// T_k = T_0
+    Constant,
+    /// T_k = T_0 α^k
+    Geometric(f64),
+    /// T_k = T_0 / ln(k + e)
+    Logarithmic,
+}
+
+impl Schedule {
+    pub fn temperature(&self, initial: f64, k: usize) -> f64 {
+        match *self {
+            Schedule::Constant => initial,
+            Schedule::Geometric(alpha) => initial * alpha.powi(k as i32),
+            Schedule::Logarithmic => initial / (k as f64 + std::f64::consts::E).ln(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BasinHoppingReport {
+    /// Best point found.
+    pub param: DVector<f64>,
+    pub cost: f64,
+    pub hops: usize,
+    /// Number of accepted hops.
+    pub accepted: usize,
+    /// Number of local searches stopped at the maximum number of iterations,
+    /// whose hops are rejected.
+    pub unconverged_searches: usize,
+}
+
+/// Basin hopping: the current point is perturbed uniformly
+/// in [-s, s] along every coordinate, a local solver is run from
+/// the perturbed point, and the local minimum found is accepted
+/// with the Metropolis criterion
+/// P = min(1, exp(-(f_new - f) / T)).
+///
+/// In simulated annealing mode there is no local search,
+/// the perturbed point itself is accepted or rejected.
+///
+/// References:
+///
+/// David J. Wales and Jonathan P. K. Doye (1997). Global Optimization
+/// by Basin-Hopping and the Lowest Energy Structures of Lennard-Jones
+/// Clusters Containing up to 110 Atoms.
+///
+/// S. Kirkpatrick, C. D. Gelatt and M. P. Vecchi (1983).
+/// Optimization by Simulated Annealing.
+pub struct BasinHopping {
+    step_size: f64,
+    temperature: f64,
+    schedule: Schedule,
+    num_hops: usize,
+    bounds: Option<Bounds>,
+    max_iters: u64,
+    seed: u64,
+}
+
+impl BasinHopping {
+    pub fn new(step_size: f64, temperature: f64, num_hops: usize) -> Self {
+        BasinHopping {
+            step_size,
+            temperature,
+            schedule: Schedule::Constant,
+            num_hops,
+            bounds: None,
+            max_iters: MAX_ITERS,
+            seed: 0,
+        }
+    }
+
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Project the perturbed points onto the bounds.
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Maximum number of iterations of every local search.
+    pub fn with_max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Basin hopping, with local solvers created by local_solver.
+    /// The local search from the initial point must converge.
+    pub fn run<F, S, G>(&self, func: &F, mut local_solver: G, param: DVector<f64>)
+        -> Result<BasinHoppingReport, Error>
+    where
+        F: Function + Clone,
+        S: Solver<F>,
+        G: FnMut() -> S
+    {
+        self.hop(param, |start| {
+            let res = func.clone().minimize(local_solver(), start, self.max_iters)?;
+            if converged(&res) {
+                Ok(Some((res.state.best_param, res.state.best_cost)))
+            }
+            else {
+                Ok(None)
+            }
+        })
+    }
+
+    /// Simulated annealing, using only the function values.
+    pub fn anneal<O>(&self, op: &O, param: DVector<f64>) -> Result<BasinHoppingReport, Error>
+    where
+        O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+    {
+        self.hop(param, |start| {
+            let cost = op.apply(&start)?;
+            Ok(Some((start, cost)))
+        })
+    }
+
+    /// Hopping loop, where minimize maps a point to its local minimum,
+    /// or to None if the local search didn't converge.
+    fn hop<M>(&self, param: DVector<f64>, mut minimize: M) -> Result<BasinHoppingReport, Error>
+    where
+        M: FnMut(DVector<f64>) -> Result<Option<(DVector<f64>, f64)>, Error>
+    {
+        if !(self.step_size > 0.0) || !self.step_size.is_finite() {
+            return Err(Error::msg("Invalid step size: it must be finite and positive."));
+        }
+        if let Some(bounds) = &self.bounds {
+            if param.len() != bounds.dimension() {
+                return Err(Error::msg("The initial point and the bounds have different dimensions."));
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let (mut current, mut current_cost) = minimize(param)?
+            .ok_or(Error::msg("The local search from the initial point didn't converge."))?;
+        let mut report = BasinHoppingReport {
+            param: current.clone(),
+            cost: current_cost,
+            hops: self.num_hops,
+            accepted: 0,
+            unconverged_searches: 0,
+        };
+
+        for k in 0 .. self.num_hops {
+            let step = self.step_size;
+            let mut perturbed = current.map(|x| x + rng.gen_range(-step ..= step));
+            if let Some(bounds) = &self.bounds {
+                perturbed = bounds.project(&perturbed);
+            }
+
+            let (candidate, candidate_cost) = match minimize(perturbed)? {
+                Some(minimum) => minimum,
+                None => {
+                    report.unconverged_searches += 1;
+                    continue;
+                },
+            };
+
+            let temperature = self.schedule.temperature(self.temperature, k);
+            let accept = candidate_cost <= current_cost
+                || (temperature > 0.0
+                    && rng.gen::<f64>() < (-(candidate_cost - current_cost) / temperature).exp());
+
+            if accept {
+                current = candidate;
+                current_cost = candidate_cost;
+                report.accepted += 1;
+
+                if current_cost < report.cost {
+                    report.param = current.clone();
+                    report.cost = current_cost;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+    use std::f64::consts::PI;
+    use crate::solvers::newton::NewtonWithModifications;
+
+    /// Rastrigin's function, with global minimum 0 at the origin
+    /// and a local minimum close to every integer point.
+    #[derive(Clone)]
+    struct Rastrigin {}
+
+    impl ArgminOp for Rastrigin {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = DMatrix<f64>;
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param.iter().map(|x| 10.0 + x * x - 10.0 * (2.0 * PI * x).cos()).sum())
+        }
+
+        fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+            Ok(param.map(|x| 2.0 * x + 20.0 * PI * (2.0 * PI * x).sin()))
+        }
+
+        fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(DMatrix::from_diagonal(&param.map(|x| 2.0 + 40.0 * PI * PI * (2.0 * PI * x).cos())))
+        }
+    }
+
+    impl Function for Rastrigin {}
+
+    /// Σ (x_i² - 1)² + 0.1 x_i, with 2ⁿ local minima close to (±1, ..., ±1).
+    #[derive(Clone)]
+    struct DoubleWell {}
+
+    impl ArgminOp for DoubleWell {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param.iter().map(|x| (x * x - 1.0).powi(2) + 0.1 * x).sum())
+        }
+    }
+
+    #[test]
+    fn test_schedule() {
+        assert_eq!(2.0, Schedule::Constant.temperature(2.0, 10));
+        assert!((Schedule::Geometric(0.5).temperature(2.0, 3) - 0.25).abs() < 1E-15);
+        assert!((Schedule::Logarithmic.temperature(2.0, 0) - 2.0).abs() < 1E-15);
+    }
+
+    #[test]
+    fn test_basin_hopping() {
+        let x0 = DVector::from_row_slice(&[4.0, -3.0]);
+        let report = BasinHopping::new(1.0, 1.0, 200)
+            .with_seed(7)
+            .run(&Rastrigin {}, NewtonWithModifications::new, x0)
+            .unwrap();
+
+        assert!(report.param.norm() < 1E-4);
+        assert!(report.accepted > 0);
+        assert_eq!(0, report.unconverged_searches);
+    }
+
+    #[test]
+    fn test_max_iters() {
+        let x0 = DVector::from_row_slice(&[4.0, -3.0]);
+
+        // a single iteration is not enough to converge from the initial point
+        assert!(BasinHopping::new(1.0, 1.0, 10)
+            .with_max_iters(1)
+            .run(&Rastrigin {}, NewtonWithModifications::new, x0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_simulated_annealing() {
+        let bounds = Bounds::new(DVector::from_element(2, -2.0), DVector::from_element(2, 2.0)).unwrap();
+        let x0 = DVector::from_row_slice(&[1.0, 1.0]);
+        let report = BasinHopping::new(0.5, 1.0, 20000)
+            .with_schedule(Schedule::Geometric(0.9995))
+            .with_bounds(bounds)
+            .with_seed(7)
+            .anneal(&DoubleWell {}, x0)
+            .unwrap();
+
+        assert!(report.param.iter().all(|&x| x < -0.9));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let x0 = DVector::from_row_slice(&[1.0, 1.0]);
+        for &step_size in [-0.5, 0.0, f64::NAN, f64::INFINITY].iter() {
+            assert!(BasinHopping::new(step_size, 1.0, 10).anneal(&DoubleWell {}, x0.clone()).is_err());
+        }
+
+        let bounds = Bounds::new(DVector::from_element(3, -2.0), DVector::from_element(3, 2.0)).unwrap();
+        assert!(BasinHopping::new(0.5, 1.0, 10)
+            .with_bounds(bounds)
+            .anneal(&DoubleWell {}, x0)
+            .is_err());
+    }
+}
@@ -0,0 +1,354 @@
+use argmin::prelude::*;
+use nalgebra::DVector;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::functions::bounds::Bounds;
+
+static MUTATION : f64 = 0.8;
+static CROSSOVER : f64 = 0.9;
+static TOLERANCE : f64 = 1E-8;
+
+/// Mutation strategies, all with binomial crossover.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Strategy {
+    /// x_r1 + F (x_r2 - x_r3)
+    Rand1Bin,
+    /// x_best + F (x_r1 - x_r2), faster but prone to premature convergence.
+    Best1Bin,
+    /// x_i + F (x_best - x_i) + F (x_r1 - x_r2)
+    CurrentToBest1Bin,
+}
+
+fn default_rng() -> StdRng {
+    StdRng::seed_from_u64(0)
+}
+
+/// Differential evolution inside box bounds.
+///
+/// The initial population is made of the initial point, projected onto
+/// the bounds, and of uniformly distributed points of the box.
+/// Every generation each member is crossed with a mutant vector,
+/// and replaced by the trial vector if it isn't worse. Trial vectors
+/// are projected onto the bounds.
+/// With dithering, the mutation factor F is drawn uniformly
+/// at every generation.
+/// The solver terminates when the costs of the whole population
+/// differ less than the tolerance. Its result can then be refined
+/// by a local solver with polish.
+///
+/// Reference:
+///
+/// Rainer Storn and Kenneth Price (1997). Differential Evolution –
+/// A Simple and Efficient Heuristic for Global Optimization over
+/// Continuous Spaces.
+#[derive(Serialize, Deserialize)]
+pub struct DifferentialEvolution {
+    bounds: Bounds,
+    strategy: Strategy,
+    population_size: usize,
+    mutation: f64,
+    crossover: f64,
+    dithering: Option<(f64, f64)>,
+    tolerance: f64,
+    population: Vec<(DVector<f64>, f64)>,
+    best: usize,
+    #[serde(skip, default = "default_rng")]
+    rng: StdRng,
+}
+
+impl DifferentialEvolution {
+    pub fn new(bounds: Bounds, population_size: usize) -> Self {
+        DifferentialEvolution {
+            bounds,
+            strategy: Strategy::Rand1Bin,
+            population_size,
+            mutation: MUTATION,
+            crossover: CROSSOVER,
+            dithering: None,
+            tolerance: TOLERANCE,
+            population: Vec::new(),
+            best: 0,
+            rng: default_rng(),
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Mutation factor F, usually in [0.5, 1].
+    pub fn with_mutation(mut self, mutation: f64) -> Self {
+        self.mutation = mutation;
+        self
+    }
+
+    /// Crossover probability CR.
+    pub fn with_crossover(mut self, crossover: f64) -> Self {
+        self.crossover = crossover;
+        self
+    }
+
+    /// Draw F uniformly in [min, max) at every generation, with min < max.
+    pub fn with_dithering(mut self, min: f64, max: f64) -> Self {
+        self.dithering = Some((min, max));
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    fn update_best(&mut self) {
+        let population = &self.population;
+        self.best = (0 .. population.len())
+            .min_by(|&i, &j| population[i].1.partial_cmp(&population[j].1).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or(0);
+    }
+
+    /// Indices of k distinct members, all different from i.
+    fn pick(&mut self, i: usize, k: usize) -> Vec<usize> {
+        let mut picked = Vec::with_capacity(k);
+        while picked.len() < k {
+            let r = self.rng.gen_range(0 .. self.population.len());
+            if r != i && !picked.contains(&r) {
+                picked.push(r);
+            }
+        }
+        picked
+    }
+
+    fn trial(&mut self, i: usize, mutation: f64) -> DVector<f64> {
+        let r = self.pick(i, 3);
+        let member = &self.population[i].0;
+        let best = &self.population[self.best].0;
+        let (x1, x2, x3) = (&self.population[r[0]].0, &self.population[r[1]].0, &self.population[r[2]].0);
+
+        let mutant = match self.strategy {
+            Strategy::Rand1Bin => x1 + mutation * (x2 - x3),
+            Strategy::Best1Bin => best + mutation * (x1 - x2),
+            Strategy::CurrentToBest1Bin => member + mutation * (best - member) + mutation * (x1 - x2),
+        };
+
+        // at least one component comes from the mutant
+        let n = member.len();
+        let forced = self.rng.gen_range(0 .. n);
+        let mut trial = member.clone();
+        for j in 0 .. n {
+            if j == forced || self.rng.gen::<f64>() < self.crossover {
+                trial[j] = mutant[j];
+            }
+        }
+
+        self.bounds.project(&trial)
+    }
+}
+
+impl<O> Solver<O> for DifferentialEvolution
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    const NAME: &'static str = "DifferentialEvolution";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        if self.population_size < 4 {
+            return Err(Error::msg("Differential evolution needs at least 4 members."));
+        }
+        if self.bounds.dimension() == 0 {
+            return Err(Error::msg("Differential evolution needs at least one variable."));
+        }
+        if !self.bounds.is_finite() {
+            return Err(Error::msg("Unable to sample the initial population: the bounds are not finite."));
+        }
+        if let Some((min, max)) = self.dithering {
+            if !(min < max) || !min.is_finite() || !max.is_finite() {
+                return Err(Error::msg("Invalid dithering: the mutation factors must be finite, with min < max."));
+            }
+        }
+
+        let param = state.get_param();
+        if param.len() != self.bounds.dimension() {
+            return Err(Error::msg("The initial point and the bounds have different dimensions."));
+        }
+
+        let param = self.bounds.project(&param);
+        let value = op.apply(&param)?;
+        self.population = vec![(param, value)];
+
+        while self.population.len() < self.population_size {
+            let member = self.bounds.sample(&mut self.rng);
+            let value = op.apply(&member)?;
+            self.population.push((member, value));
+        }
+        self.update_best();
+
+        let (best, best_value) = self.population[self.best].clone();
+        Ok(Some(ArgminIterData::new().param(best).cost(best_value)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let mutation = match self.dithering {
+            Some((min, max)) => self.rng.gen_range(min .. max),
+            None => self.mutation,
+        };
+
+        let mut next_population = self.population.clone();
+        for i in 0 .. self.population.len() {
+            let trial = self.trial(i, mutation);
+            let value = op.apply(&trial)?;
+
+            if value <= self.population[i].1 {
+                next_population[i] = (trial, value);
+            }
+        }
+        self.population = next_population;
+        self.update_best();
+
+        let (best, best_value) = self.population[self.best].clone();
+        Ok(ArgminIterData::new().param(best).cost(best_value))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason
+    {
+        let worst_value = self.population
+            .iter()
+            .map(|(_, value)| *value)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        if worst_value - self.population[self.best].1 <= self.tolerance {
+            TerminationReason::TargetToleranceReached
+        }
+        else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+    use std::f64::consts::PI;
+    use crate::functions::Function;
+    use crate::solvers::global::polish;
+    use crate::solvers::newton::NewtonWithModifications;
+
+    /// Rastrigin's function, with global minimum 0 at the origin
+    /// and a local minimum close to every integer point.
+    #[derive(Clone)]
+    struct Rastrigin {}
+
+    impl ArgminOp for Rastrigin {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = DMatrix<f64>;
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param.iter().map(|x| 10.0 + x * x - 10.0 * (2.0 * PI * x).cos()).sum())
+        }
+
+        fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+            Ok(param.map(|x| 2.0 * x + 20.0 * PI * (2.0 * PI * x).sin()))
+        }
+
+        fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(DMatrix::from_diagonal(&param.map(|x| 2.0 + 40.0 * PI * PI * (2.0 * PI * x).cos())))
+        }
+    }
+
+    impl Function for Rastrigin {}
+
+    fn bounds() -> Bounds {
+        Bounds::new(DVector::from_element(2, -5.12), DVector::from_element(2, 5.12)).unwrap()
+    }
+
+    #[test]
+    fn test_rastrigin() {
+        let solvers = vec![
+            DifferentialEvolution::new(bounds(), 30).with_seed(1),
+            DifferentialEvolution::new(bounds(), 30)
+                .with_strategy(Strategy::CurrentToBest1Bin)
+                .with_dithering(0.5, 1.0)
+                .with_seed(1),
+        ];
+
+        for solver in solvers {
+            let x0 = DVector::from_row_slice(&[4.0, -3.0]);
+            let res = Executor::new(Rastrigin {}, solver, x0)
+                .max_iters(1000)
+                .run()
+                .unwrap();
+
+            assert_eq!(TerminationReason::TargetToleranceReached, res.state.termination_reason);
+            assert!(res.state.best_param.norm() < 1E-4);
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        let x0 = DVector::from_row_slice(&[4.0, -3.0]);
+        let unbounded = Bounds::new(DVector::from_element(2, -5.12), DVector::from_element(2, f64::INFINITY)).unwrap();
+
+        let solvers = vec![
+            DifferentialEvolution::new(unbounded, 30),
+            DifferentialEvolution::new(bounds(), 30).with_dithering(1.0, 0.5),
+            DifferentialEvolution::new(bounds(), 30).with_dithering(0.5, 0.5),
+        ];
+
+        for solver in solvers {
+            assert!(Executor::new(Rastrigin {}, solver, x0.clone()).max_iters(10).run().is_err());
+        }
+
+        let x0 = DVector::from_row_slice(&[4.0, -3.0, 1.0]);
+        assert!(Executor::new(Rastrigin {}, DifferentialEvolution::new(bounds(), 30), x0)
+            .max_iters(10)
+            .run()
+            .is_err());
+
+        let empty = Bounds::new(DVector::zeros(0), DVector::zeros(0)).unwrap();
+        assert!(Executor::new(Rastrigin {}, DifferentialEvolution::new(empty, 30), DVector::zeros(0))
+            .max_iters(10)
+            .run()
+            .is_err());
+    }
+
+    #[test]
+    fn test_polish() {
+        let x0 = DVector::from_row_slice(&[4.0, -3.0]);
+        let solver = DifferentialEvolution::new(bounds(), 30)
+            .with_tolerance(1E-2)
+            .with_seed(1);
+        let res = Executor::new(Rastrigin {}, solver, x0)
+            .max_iters(1000)
+            .run()
+            .unwrap();
+
+        let (param, cost) = polish(Rastrigin {}, NewtonWithModifications::new(), &bounds(), res.state.best_param.clone(), 100)
+            .unwrap();
+        assert!(cost <= res.state.best_cost);
+        assert!(param.norm() < 1E-6);
+
+        // an unconverged local solution is discarded
+        let (param, cost) = polish(Rastrigin {}, NewtonWithModifications::new(), &bounds(), res.state.best_param.clone(), 0)
+            .unwrap();
+        assert_eq!(res.state.best_param, param);
+        assert_eq!(res.state.best_cost, cost);
+    }
+}
@@ -0,0 +1,11 @@
+mod basinhopping;
+mod differentialevolution;
+mod multistart;
+mod particleswarm;
+mod polish;
+
+pub use basinhopping::*;
+pub use differentialevolution::*;
+pub use multistart::*;
+pub use particleswarm::*;
+pub use polish::*;
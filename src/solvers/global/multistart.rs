@@ -0,0 +1,285 @@
+use argmin::prelude::*;
+use nalgebra::DVector;
+use rand::prelude::*;
+
+use crate::functions::{converged, Function};
+use crate::functions::bounds::Bounds;
+
+/// Relative distance below which two local minima are the same.
+static TOLERANCE : f64 = 1E-4;
+static MAX_ITERS : u64 = 1000;
+
+/// Distribution of the starting points inside the bounds.
+#[derive(Clone, Copy, Debug)]
+pub enum Sampling {
+    Uniform,
+    /// Every coordinate interval is split in as many strata
+    /// as starting points, with exactly one point in each of them.
+    LatinHypercube,
+}
+
+#[derive(Clone, Debug)]
+pub struct LocalMinimum {
+    pub param: DVector<f64>,
+    pub cost: f64,
+    /// Number of starting points in its basin.
+    pub hits: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct MultiStartReport {
+    /// Distinct local minima, sorted by cost.
+    pub minima: Vec<LocalMinimum>,
+    /// Number of local searches actually run.
+    pub local_searches: usize,
+    /// Number of local searches stopped at the maximum number of iterations,
+    /// whose final points are not among the minima.
+    pub unconverged_searches: usize,
+}
+
+impl MultiStartReport {
+    pub fn best(&self) -> Option<&LocalMinimum> {
+        self.minima.first()
+    }
+}
+
+/// Multi-start global search: a local solver is run from several
+/// starting points inside the bounds, and the local minima found
+/// are collected with the number of starting points in their basins.
+///
+/// With clustering, the starting points are processed by increasing cost,
+/// and no local search is run from a point closer than the cluster radius
+/// to a lower local minimum already found: the point is assumed
+/// to lie in its basin.
+///
+/// Reference:
+///
+/// A. H. G. Rinnooy Kan and G. T. Timmer (1987). Stochastic global
+/// optimization methods part I: Clustering methods.
+pub struct MultiStart {
+    bounds: Bounds,
+    num_starts: usize,
+    sampling: Sampling,
+    cluster_radius: Option<f64>,
+    tolerance: f64,
+    max_iters: u64,
+    seed: u64,
+}
+
+impl MultiStart {
+    pub fn new(bounds: Bounds, num_starts: usize) -> Self {
+        MultiStart {
+            bounds,
+            num_starts,
+            sampling: Sampling::Uniform,
+            cluster_radius: None,
+            tolerance: TOLERANCE,
+            max_iters: MAX_ITERS,
+            seed: 0,
+        }
+    }
+
+    pub fn with_sampling(mut self, sampling: Sampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    pub fn with_clustering(mut self, cluster_radius: f64) -> Self {
+        self.cluster_radius = Some(cluster_radius);
+        self
+    }
+
+    /// Two local minima are the same if their distance is less than
+    /// tolerance (1 + ‖x‖).
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Maximum number of iterations of every local search.
+    pub fn with_max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn starting_points<R: Rng>(&self, rng: &mut R) -> Result<Vec<DVector<f64>>, Error> {
+        if !self.bounds.is_finite() {
+            return Err(Error::msg("Unable to sample the starting points: the bounds are not finite."));
+        }
+
+        let points = match self.sampling {
+            Sampling::Uniform => (0 .. self.num_starts).map(|_| self.bounds.sample(rng)).collect(),
+            Sampling::LatinHypercube => {
+                let (lower, upper) = (self.bounds.lower(), self.bounds.upper());
+                let mut points = vec![DVector::zeros(self.bounds.dimension()); self.num_starts];
+
+                for j in 0 .. self.bounds.dimension() {
+                    let mut strata: Vec<usize> = (0 .. self.num_starts).collect();
+                    strata.shuffle(rng);
+
+                    for (point, stratum) in points.iter_mut().zip(strata) {
+                        let t = (stratum as f64 + rng.gen::<f64>()) / self.num_starts as f64;
+                        point[j] = lower[j] + t * (upper[j] - lower[j]);
+                    }
+                }
+                points
+            },
+        };
+
+        Ok(points)
+    }
+
+    /// Run a local solver, created by local_solver, from every starting point.
+    pub fn run<F, S, G>(&self, func: &F, mut local_solver: G) -> Result<MultiStartReport, Error>
+    where
+        F: Function + Clone,
+        S: Solver<F>,
+        G: FnMut() -> S
+    {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        let mut starts = self.starting_points(&mut rng)?
+            .into_iter()
+            .map(|start| Ok((func.apply(&start)?, start)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        if self.cluster_radius.is_some() {
+            starts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        }
+
+        let mut minima: Vec<LocalMinimum> = Vec::new();
+        let mut local_searches = 0;
+        let mut unconverged_searches = 0;
+        for (value, start) in starts {
+            if let Some(radius) = self.cluster_radius {
+                let cluster = minima
+                    .iter_mut()
+                    .find(|minimum| minimum.cost <= value && (&minimum.param - &start).norm() <= radius);
+
+                if let Some(minimum) = cluster {
+                    minimum.hits += 1;
+                    continue;
+                }
+            }
+
+            let res = func.clone().minimize(local_solver(), start, self.max_iters)?;
+            local_searches += 1;
+            if !converged(&res) {
+                unconverged_searches += 1;
+                continue;
+            }
+
+            let (param, cost) = (res.state.best_param, res.state.best_cost);
+            let tolerance = self.tolerance * (1.0 + param.norm());
+
+            match minima.iter_mut().find(|minimum| (&minimum.param - &param).norm() <= tolerance) {
+                Some(minimum) => {
+                    minimum.hits += 1;
+                    if cost < minimum.cost {
+                        minimum.param = param;
+                        minimum.cost = cost;
+                    }
+                },
+                None => minima.push(LocalMinimum { param, cost, hits: 1 }),
+            }
+        }
+
+        minima.sort_by(|a, b| a.cost.partial_cmp(&b.cost).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(MultiStartReport { minima, local_searches, unconverged_searches })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::DMatrix;
+    use crate::solvers::newton::NewtonWithModifications;
+
+    /// Σ (x_i² - 1)² + 0.1 x_i, with 2ⁿ local minima close to (±1, ..., ±1).
+    #[derive(Clone)]
+    struct DoubleWell {}
+
+    impl ArgminOp for DoubleWell {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = DMatrix<f64>;
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param.iter().map(|x| (x * x - 1.0).powi(2) + 0.1 * x).sum())
+        }
+
+        fn gradient(&self, param: &Self::Param) -> Result<Self::Param, Error> {
+            Ok(param.map(|x| 4.0 * x * (x * x - 1.0) + 0.1))
+        }
+
+        fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(DMatrix::from_diagonal(&param.map(|x| 12.0 * x * x - 4.0)))
+        }
+    }
+
+    impl Function for DoubleWell {}
+
+    fn bounds() -> Bounds {
+        Bounds::new(DVector::from_element(2, -2.0), DVector::from_element(2, 2.0)).unwrap()
+    }
+
+    #[test]
+    fn test_latin_hypercube() {
+        let multistart = MultiStart::new(bounds(), 10).with_sampling(Sampling::LatinHypercube);
+        let points = multistart.starting_points(&mut StdRng::seed_from_u64(0)).unwrap();
+
+        for j in 0 .. 2 {
+            let mut strata: Vec<usize> = points
+                .iter()
+                .map(|point| ((point[j] + 2.0) / 4.0 * 10.0) as usize)
+                .collect();
+            strata.sort();
+            assert_eq!((0 .. 10).collect::<Vec<_>>(), strata);
+        }
+    }
+
+    #[test]
+    fn test_double_well() {
+        let multistart = MultiStart::new(bounds(), 40).with_seed(42);
+        let report = multistart.run(&DoubleWell {}, NewtonWithModifications::new).unwrap();
+
+        assert_eq!(4, report.minima.len());
+        assert_eq!(40, report.local_searches);
+        assert_eq!(0, report.unconverged_searches);
+        assert_eq!(40, report.minima.iter().map(|minimum| minimum.hits).sum::<usize>());
+
+        let best = report.best().unwrap();
+        assert!(best.param.iter().all(|&x| x < -0.9));
+    }
+
+    #[test]
+    fn test_clustering() {
+        let multistart = MultiStart::new(bounds(), 40)
+            .with_sampling(Sampling::LatinHypercube)
+            .with_clustering(0.8)
+            .with_seed(42);
+        let report = multistart.run(&DoubleWell {}, NewtonWithModifications::new).unwrap();
+
+        assert_eq!(4, report.minima.len());
+        assert!(report.local_searches < 40);
+        assert_eq!(40, report.minima.iter().map(|minimum| minimum.hits).sum::<usize>());
+    }
+
+    #[test]
+    fn test_max_iters() {
+        // a single iteration is not enough to converge from a random point
+        let multistart = MultiStart::new(bounds(), 10).with_max_iters(1).with_seed(42);
+        let report = multistart.run(&DoubleWell {}, NewtonWithModifications::new).unwrap();
+
+        assert_eq!(10, report.local_searches);
+        assert_eq!(10, report.unconverged_searches);
+        assert!(report.minima.is_empty());
+    }
+}
@@ -0,0 +1,359 @@
+use argmin::prelude::*;
+use nalgebra::DVector;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::functions::bounds::Bounds;
+
+/// Maximum speed, relative to the width of the bounds.
+static VELOCITY_CLAMP : f64 = 0.5;
+static TOLERANCE : f64 = 1E-8;
+
+/// Velocity update rules, with personal best p and neighbourhood best l.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Variant {
+    /// v ← w v + c1 r1 (p - x) + c2 r2 (l - x)
+    InertiaWeight { inertia: f64, cognitive: f64, social: f64 },
+    /// v ← χ (v + φ1 r1 (p - x) + φ2 r2 (l - x)),
+    /// with χ = 2 / |2 - φ - √(φ² - 4φ)| and φ = φ1 + φ2 > 4.
+    Constriction { cognitive: f64, social: f64 },
+}
+
+impl Variant {
+    /// Inertia, cognitive and social coefficients.
+    fn coefficients(&self) -> Result<(f64, f64, f64), Error> {
+        match *self {
+            Variant::InertiaWeight { inertia, cognitive, social } => Ok((inertia, cognitive, social)),
+            Variant::Constriction { cognitive, social } => {
+                let phi = cognitive + social;
+                if phi <= 4.0 {
+                    return Err(Error::msg("The constriction coefficients must add up to more than 4."));
+                }
+
+                let chi = 2.0 / (2.0 - phi - (phi * phi - 4.0 * phi).sqrt()).abs();
+                Ok((chi, chi * cognitive, chi * social))
+            },
+        }
+    }
+}
+
+/// Neighbourhood of every particle.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Topology {
+    /// The whole swarm.
+    Global,
+    /// The particle and its two neighbours, slower but less prone
+    /// to premature convergence.
+    Ring,
+}
+
+fn default_rng() -> StdRng {
+    StdRng::seed_from_u64(0)
+}
+
+/// Particle swarm optimization inside box bounds.
+///
+/// The first particle starts at the initial point, projected onto the bounds,
+/// the others at uniformly distributed points of the box, all at rest.
+/// The velocities are clamped to a fraction of the width of the bounds,
+/// and a particle leaving the box is stopped on its boundary.
+/// Every iteration reports the best position of the swarm,
+/// the solver terminates when all the personal bests are closer
+/// than the tolerance to it.
+///
+/// Reference:
+///
+/// Maurice Clerc and James Kennedy (2002). The particle swarm - explosion,
+/// stability, and convergence in a multidimensional complex space.
+#[derive(Serialize, Deserialize)]
+pub struct ParticleSwarm {
+    bounds: Bounds,
+    num_particles: usize,
+    variant: Variant,
+    topology: Topology,
+    velocity_clamp: f64,
+    tolerance: f64,
+    positions: Vec<DVector<f64>>,
+    velocities: Vec<DVector<f64>>,
+    personal_best: Vec<(DVector<f64>, f64)>,
+    best: usize,
+    #[serde(skip, default = "default_rng")]
+    rng: StdRng,
+}
+
+impl ParticleSwarm {
+    pub fn new(bounds: Bounds, num_particles: usize) -> Self {
+        ParticleSwarm {
+            bounds,
+            num_particles,
+            variant: Variant::InertiaWeight { inertia: 0.7298, cognitive: 1.49618, social: 1.49618 },
+            topology: Topology::Global,
+            velocity_clamp: VELOCITY_CLAMP,
+            tolerance: TOLERANCE,
+            positions: Vec::new(),
+            velocities: Vec::new(),
+            personal_best: Vec::new(),
+            best: 0,
+            rng: default_rng(),
+        }
+    }
+
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Maximum speed along every coordinate, relative to the width of the bounds.
+    pub fn with_velocity_clamp(mut self, velocity_clamp: f64) -> Self {
+        self.velocity_clamp = velocity_clamp;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    fn update_best(&mut self) {
+        let personal_best = &self.personal_best;
+        self.best = (0 .. personal_best.len())
+            .min_by(|&i, &j| personal_best[i].1.partial_cmp(&personal_best[j].1).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or(0);
+    }
+
+    /// Index of the best personal best in the neighbourhood of particle i.
+    fn neighbourhood_best(&self, i: usize) -> usize {
+        match self.topology {
+            Topology::Global => self.best,
+            Topology::Ring => {
+                let n = self.personal_best.len();
+                let neighbours = [(i + n - 1) % n, i, (i + 1) % n];
+
+                *neighbours
+                    .iter()
+                    .min_by(|&&j, &&k| {
+                        self.personal_best[j].1
+                            .partial_cmp(&self.personal_best[k].1)
+                            .unwrap_or(std::cmp::Ordering::Equal)
+                    })
+                    .unwrap()
+            },
+        }
+    }
+}
+
+impl<O> Solver<O> for ParticleSwarm
+where
+    O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    const NAME: &'static str = "ParticleSwarm";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        if self.num_particles == 0 {
+            return Err(Error::msg("The swarm needs at least one particle."));
+        }
+        if !self.bounds.is_finite() {
+            return Err(Error::msg("Unable to place the particles: the bounds are not finite."));
+        }
+        self.variant.coefficients()?;
+
+        let param = state.get_param();
+        if param.len() != self.bounds.dimension() {
+            return Err(Error::msg("The initial point and the bounds have different dimensions."));
+        }
+
+        let param = self.bounds.project(&param);
+        self.positions = vec![param];
+        while self.positions.len() < self.num_particles {
+            let position = self.bounds.sample(&mut self.rng);
+            self.positions.push(position);
+        }
+
+        self.velocities = vec![DVector::zeros(self.bounds.dimension()); self.num_particles];
+        self.personal_best = self.positions
+            .iter()
+            .map(|position| Ok((position.clone(), op.apply(position)?)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        self.update_best();
+
+        let (best, best_value) = self.personal_best[self.best].clone();
+        Ok(Some(ArgminIterData::new().param(best).cost(best_value)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        _state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let (inertia, cognitive, social) = self.variant.coefficients()?;
+        let (lower, upper) = (self.bounds.lower().clone(), self.bounds.upper().clone());
+        let max_speed = self.velocity_clamp * (&upper - &lower);
+
+        for i in 0 .. self.positions.len() {
+            let neighbourhood_best = self.neighbourhood_best(i);
+
+            for j in 0 .. lower.len() {
+                let r1 = self.rng.gen::<f64>();
+                let r2 = self.rng.gen::<f64>();
+                let x = self.positions[i][j];
+
+                let velocity = inertia * self.velocities[i][j]
+                    + cognitive * r1 * (self.personal_best[i].0[j] - x)
+                    + social * r2 * (self.personal_best[neighbourhood_best].0[j] - x);
+                self.velocities[i][j] = velocity.max(-max_speed[j]).min(max_speed[j]);
+
+                self.positions[i][j] += self.velocities[i][j];
+                if self.positions[i][j] < lower[j] || self.positions[i][j] > upper[j] {
+                    self.positions[i][j] = self.positions[i][j].max(lower[j]).min(upper[j]);
+                    self.velocities[i][j] = 0.0;
+                }
+            }
+
+            let value = op.apply(&self.positions[i])?;
+            if value < self.personal_best[i].1 {
+                self.personal_best[i] = (self.positions[i].clone(), value);
+            }
+        }
+        self.update_best();
+
+        let (best, best_value) = self.personal_best[self.best].clone();
+        Ok(ArgminIterData::new().param(best).cost(best_value))
+    }
+
+    fn terminate(&mut self, _state: &IterState<O>) -> TerminationReason
+    {
+        let best = &self.personal_best[self.best].0;
+        let spread = self.personal_best
+            .iter()
+            .map(|(position, _)| (position - best).norm())
+            .fold(0.0, f64::max);
+
+        if spread <= self.tolerance {
+            TerminationReason::TargetToleranceReached
+        }
+        else {
+            TerminationReason::NotTerminated
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+    use std::sync::{Arc, Mutex};
+
+    /// Rastrigin's function, with global minimum 0 at the origin
+    /// and a local minimum close to every integer point.
+    #[derive(Clone)]
+    struct Rastrigin {}
+
+    impl ArgminOp for Rastrigin {
+        type Param = DVector<f64>;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param.iter().map(|x| 10.0 + x * x - 10.0 * (2.0 * PI * x).cos()).sum())
+        }
+    }
+
+    #[test]
+    fn test_rastrigin() {
+        let bounds = Bounds::new(DVector::from_element(2, -5.12), DVector::from_element(2, 5.12)).unwrap();
+        let solvers = vec![
+            ParticleSwarm::new(bounds.clone(), 40).with_seed(3),
+            ParticleSwarm::new(bounds, 40)
+                .with_variant(Variant::Constriction { cognitive: 2.05, social: 2.05 })
+                .with_topology(Topology::Ring)
+                .with_seed(3),
+        ];
+
+        for solver in solvers {
+            let x0 = DVector::from_row_slice(&[4.0, -3.0]);
+            let res = Executor::new(Rastrigin {}, solver, x0)
+                .max_iters(2000)
+                .run()
+                .unwrap();
+
+            assert_eq!(TerminationReason::TargetToleranceReached, res.state.termination_reason);
+            assert!(res.state.best_param.norm() < 1E-6);
+        }
+    }
+
+    /// Records the cost reported at every iteration.
+    struct History {
+        costs: Arc<Mutex<Vec<f64>>>,
+    }
+
+    impl<O: ArgminOp<Output = f64>> Observe<O> for History {
+        fn observe_iter(&mut self, state: &IterState<O>, _kv: &ArgminKV) -> Result<(), Error> {
+            self.costs.lock().unwrap().push(state.cost);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_history() {
+        let bounds = Bounds::new(DVector::from_element(2, -5.12), DVector::from_element(2, 5.12)).unwrap();
+        let costs = Arc::new(Mutex::new(Vec::new()));
+        let history = History { costs: costs.clone() };
+
+        let x0 = DVector::from_row_slice(&[4.0, -3.0]);
+        let res = Executor::new(Rastrigin {}, ParticleSwarm::new(bounds, 40).with_seed(3), x0)
+            .add_observer(history, ObserverMode::Always)
+            .max_iters(50)
+            .run()
+            .unwrap();
+
+        // the best position of the swarm never gets worse
+        let costs = costs.lock().unwrap();
+        assert_eq!(res.state.iter as usize, costs.len());
+        assert!(costs.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert_eq!(res.state.best_cost, costs[costs.len() - 1]);
+    }
+
+    #[test]
+    fn test_unbounded() {
+        let bounds = Bounds::new(DVector::from_element(2, f64::NEG_INFINITY), DVector::from_element(2, 5.12)).unwrap();
+        let x0 = DVector::from_row_slice(&[4.0, -3.0]);
+
+        assert!(Executor::new(Rastrigin {}, ParticleSwarm::new(bounds, 40), x0)
+            .max_iters(10)
+            .run()
+            .is_err());
+    }
+
+    #[test]
+    fn test_invalid_constriction() {
+        let variant = Variant::Constriction { cognitive: 1.0, social: 2.0 };
+        assert!(variant.coefficients().is_err());
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let bounds = Bounds::new(DVector::from_element(2, -5.12), DVector::from_element(2, 5.12)).unwrap();
+        let x0 = DVector::from_row_slice(&[4.0, -3.0, 1.0]);
+
+        assert!(Executor::new(Rastrigin {}, ParticleSwarm::new(bounds, 40), x0)
+            .max_iters(10)
+            .run()
+            .is_err());
+    }
+}
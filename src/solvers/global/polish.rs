@@ -0,0 +1,31 @@
+use argmin::prelude::*;
+use nalgebra::DVector;
+
+use crate::functions::{converged, Function};
+use crate::functions::bounds::Bounds;
+
+/// Refine the solution of a global optimizer with a local solver,
+/// run for at most max_iters iterations.
+/// The local solution is kept only if the local solver converged,
+/// and the solution lies inside the bounds and improves the cost,
+/// otherwise param is returned unchanged.
+///
+/// Polishing is not an option of the global solvers, since the local
+/// solvers need derivatives the global ones don't, and an executor
+/// of their own: it's run on the result of the global solver instead.
+pub fn polish<F, S>(func: F, solver: S, bounds: &Bounds, param: DVector<f64>, max_iters: u64)
+    -> Result<(DVector<f64>, f64), Error>
+where
+    F: Function + Clone,
+    S: Solver<F>
+{
+    let cost = func.apply(&param)?;
+    let res = func.minimize(solver, param.clone(), max_iters)?;
+
+    if converged(&res) && bounds.contains(&res.state.best_param) && res.state.best_cost < cost {
+        Ok((res.state.best_param, res.state.best_cost))
+    }
+    else {
+        Ok((param, cost))
+    }
+}
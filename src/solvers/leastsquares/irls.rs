@@ -0,0 +1,123 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use linear_search_solver::Solverf64;
+use crate::solvers::newton::cholesky;
+use crate::solvers::linesearch::LineSearch;
+use crate::steplength::backtracking;
+
+static DELTA : f64 = 1E-4;
+static BETA : f64 = 100.0;
+
+/// Iteratively reweighted least squares, to be used with
+/// `functions::leastsquares::RobustLeastSquares`.
+///
+/// Every iteration solves the weighted normal equations
+/// Jᵀ W J p = -Jᵀ W r, with the weights of the current residuals,
+/// and takes the full step whenever it decreases the cost.
+/// Otherwise it falls back to backtracking, which makes it
+/// a scaled Gauss-Newton method.
+#[derive(Serialize, Deserialize, Solverf64)]
+pub struct Irls {
+}
+
+impl Irls {
+    pub fn new() -> Self {
+        Irls {}
+    }
+}
+
+impl<O> LineSearch<O, f64> for Irls
+where
+    O: ArgminOp<
+        Output = f64,
+        Float = f64,
+        Param = DVector<f64>,
+        Hessian = DMatrix<f64>
+    >
+{
+    fn descent_dir(
+        &mut self,
+        _op: &mut OpWrapper<O>,
+        state: &IterState<O>
+    ) -> Result<O::Param, Error> {
+        let gradient = state.grad
+            .as_ref()
+            .ok_or(Error::msg("gradient unavailable"))?;
+
+        // The Gauss-Newton Hessian Jᵀ W J is positive semidefinite,
+        // the modified factorization only kicks in when it's singular,
+        // e.g. when Tukey's loss discards too many residuals.
+        let hessian = state.hessian
+            .as_ref()
+            .ok_or(Error::msg("hessian unavailable"))?;
+
+        let (mat_l, vec_d) = cholesky::factorization(&hessian, DELTA, BETA)?;
+        let descent_dir = cholesky::solve(&mat_l, &vec_d, &(-gradient))?;
+
+        Ok(descent_dir)
+    }
+
+    fn step_length(&self, op: &mut OpWrapper<O>, state: &IterState<O>, descent_dir: &O::Param)
+        -> Result<O::Float, Error>
+    {
+        let next_param = &state.param + descent_dir;
+        if op.apply(&next_param)? < state.cost {
+            Ok(1.0)
+        }
+        else {
+            backtracking::step_length(op, state, descent_dir, 1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::Function;
+    use crate::functions::leastsquares::{LeastSquares, Loss, RobustLeastSquares};
+
+    /// Straight line fit y = a x + b.
+    #[derive(Clone)]
+    struct Line {
+        x: DVector<f64>,
+        y: DVector<f64>,
+    }
+
+    impl LeastSquares for Line {
+        fn residuals(&self, param: &DVector<f64>) -> Result<DVector<f64>, Error> {
+            Ok(self.x.map(|x| param[0] * x + param[1]) - &self.y)
+        }
+
+        fn jacobian(&self, _param: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+            let mut jacobian = DMatrix::from_element(self.x.len(), 2, 1.0);
+            jacobian.set_column(0, &self.x);
+            Ok(jacobian)
+        }
+    }
+
+    #[test]
+    fn test_outliers() {
+        let x = DVector::from_fn(20, |i, _| i as f64);
+        let mut y = x.map(|x| 2.0 * x - 1.0);
+        y[3] = 100.0;
+        y[15] = -50.0;
+
+        let problem = Line { x, y };
+        let x0 = DVector::from_row_slice(&[0.0, 0.0]);
+
+        for loss in [Loss::Huber(1.0), Loss::Cauchy(1.0), Loss::SoftL1(1.0)].iter() {
+            let cost = RobustLeastSquares::new(problem.clone(), *loss);
+            let res = cost.solve(Irls::new(), x0.clone());
+
+            let param = &res.state.best_param;
+            assert!((param[0] - 2.0).abs() < 5E-2);
+            assert!((param[1] + 1.0).abs() < 5E-1);
+
+            let weights = res.operator.weights(param).unwrap();
+            assert!(weights[3] < 0.1);
+            assert!(weights[15] < 0.1);
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod irls;
+mod varpro;
+
+pub use irls::*;
+pub use varpro::*;
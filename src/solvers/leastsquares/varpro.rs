@@ -0,0 +1,193 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+
+use crate::functions::leastsquares::LeastSquares;
+
+/// Separable least-squares problem, whose residuals
+/// r(α, c) = Φ(α) c - y
+/// are linear in the coefficients c and nonlinear in the parameters α.
+pub trait SeparableLeastSquares : Clone {
+    /// The observations y.
+    fn observations(&self) -> &DVector<f64>;
+
+    /// The matrix Φ(α), with one column per linear coefficient.
+    fn basis(&self, alpha: &DVector<f64>) -> Result<DMatrix<f64>, Error>;
+
+    /// The derivative ∂Φ/∂α_k of the basis matrix.
+    fn basis_derivative(&self, alpha: &DVector<f64>, k: usize) -> Result<DMatrix<f64>, Error>;
+}
+
+/// Variable projection for separable least-squares problems.
+///
+/// The linear coefficients are eliminated with a QR solve,
+/// c(α) = Φ(α)⁺ y, leaving the projected residuals
+/// r(α) = -P⊥(α) y,
+/// which only depend on the nonlinear parameters.
+/// Since this is again a least-squares problem, it can be solved with
+/// `RobustLeastSquares` and any of the Gauss-Newton type solvers.
+///
+/// Reference:
+///
+/// Gene Golub and Victor Pereyra (2003). Separable nonlinear least squares:
+/// the variable projection method and its applications.
+#[derive(Clone)]
+pub struct VariableProjection<P>
+where
+    P: SeparableLeastSquares
+{
+    problem: P,
+}
+
+impl<P> VariableProjection<P>
+where
+    P: SeparableLeastSquares
+{
+    pub fn new(problem: P) -> Self {
+        VariableProjection { problem }
+    }
+
+    pub fn problem(&self) -> &P {
+        &self.problem
+    }
+
+    /// Recovers the linear coefficients c(α) for the given nonlinear
+    /// parameters, typically the solution of the projected problem.
+    pub fn linear_coefficients(&self, alpha: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        let (mat_q, mat_r) = self.factorization(alpha)?;
+        let qty = mat_q.transpose() * self.problem.observations();
+
+        mat_r.solve_upper_triangular(&qty)
+            .ok_or(Error::msg("Unable to compute the linear coefficients: the basis is rank deficient."))
+    }
+
+    /// Thin QR factorization of the basis matrix.
+    fn factorization(&self, alpha: &DVector<f64>) -> Result<(DMatrix<f64>, DMatrix<f64>), Error> {
+        let basis = self.problem.basis(alpha)?;
+        if basis.nrows() < basis.ncols() {
+            return Err(Error::msg("Unable to project the linear coefficients: there are fewer observations than coefficients."));
+        }
+
+        let qr = basis.qr();
+        Ok((qr.q(), qr.r()))
+    }
+}
+
+impl<P> LeastSquares for VariableProjection<P>
+where
+    P: SeparableLeastSquares
+{
+    fn residuals(&self, alpha: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        let (mat_q, _) = self.factorization(alpha)?;
+        let y = self.problem.observations();
+
+        Ok(&mat_q * (mat_q.transpose() * y) - y)
+    }
+
+    /// Full Golub-Pereyra Jacobian, whose k-th column is
+    /// P⊥ ∂Φ/∂α_k c - (Φ⁺)ᵀ (∂Φ/∂α_k)ᵀ r.
+    fn jacobian(&self, alpha: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+        let (mat_q, mat_r) = self.factorization(alpha)?;
+        let y = self.problem.observations();
+
+        let coefficients = mat_r.solve_upper_triangular(&(mat_q.transpose() * y))
+            .ok_or(Error::msg("Unable to compute the linear coefficients: the basis is rank deficient."))?;
+        let residuals = &mat_q * (mat_q.transpose() * y) - y;
+        let mat_r_t = mat_r.transpose();
+
+        let mut jacobian = DMatrix::zeros(y.len(), alpha.len());
+        for k in 0 .. alpha.len() {
+            let derivative = self.problem.basis_derivative(alpha, k)?;
+
+            let dc = &derivative * &coefficients;
+            let projected = &dc - &mat_q * (mat_q.transpose() * &dc);
+
+            let dtr = derivative.transpose() * &residuals;
+            let correction = &mat_q * mat_r_t.solve_lower_triangular(&dtr)
+                .ok_or(Error::msg("Unable to compute the Jacobian: the basis is rank deficient."))?;
+
+            jacobian.set_column(k, &(projected - correction));
+        }
+
+        Ok(jacobian)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::Function;
+    use crate::functions::leastsquares::{Loss, RobustLeastSquares};
+    use crate::solvers::leastsquares::Irls;
+
+    /// Sum of exponentials y(t) = Σ c_k exp(-α_k t).
+    #[derive(Clone)]
+    struct Exponentials {
+        t: DVector<f64>,
+        y: DVector<f64>,
+    }
+
+    impl Exponentials {
+        fn new(alpha: &[f64], c: &[f64]) -> Self {
+            let t = DVector::from_fn(30, |i, _| 0.1 * i as f64);
+            let y = t.map(|t| {
+                alpha.iter()
+                    .zip(c.iter())
+                    .map(|(a, c)| c * (-a * t).exp())
+                    .sum()
+            });
+
+            Exponentials { t, y }
+        }
+    }
+
+    impl SeparableLeastSquares for Exponentials {
+        fn observations(&self) -> &DVector<f64> {
+            &self.y
+        }
+
+        fn basis(&self, alpha: &DVector<f64>) -> Result<DMatrix<f64>, Error> {
+            Ok(DMatrix::from_fn(self.t.len(), alpha.len(), |i, k| (-alpha[k] * self.t[i]).exp()))
+        }
+
+        fn basis_derivative(&self, alpha: &DVector<f64>, k: usize) -> Result<DMatrix<f64>, Error> {
+            let mut derivative = DMatrix::zeros(self.t.len(), alpha.len());
+            for i in 0 .. self.t.len() {
+                derivative[(i, k)] = -self.t[i] * (-alpha[k] * self.t[i]).exp();
+            }
+            Ok(derivative)
+        }
+    }
+
+    #[test]
+    fn test_jacobian() {
+        let varpro = VariableProjection::new(Exponentials::new(&[1.0, 3.0], &[2.0, -1.0]));
+        let alpha = DVector::from_row_slice(&[0.7, 2.2]);
+
+        let jacobian = varpro.jacobian(&alpha).unwrap();
+
+        let h = 1E-6;
+        for k in 0 .. alpha.len() {
+            let mut forward = alpha.clone();
+            forward[k] += h;
+            let mut backward = alpha.clone();
+            backward[k] -= h;
+
+            let column = (varpro.residuals(&forward).unwrap() - varpro.residuals(&backward).unwrap())
+                / (2.0 * h);
+            assert!((column - jacobian.column(k)).norm() < 1E-6);
+        }
+    }
+
+    #[test]
+    fn test_exponentials() {
+        let varpro = VariableProjection::new(Exponentials::new(&[1.0, 3.0], &[2.0, -1.0]));
+        let cost = RobustLeastSquares::new(varpro, Loss::Linear);
+
+        let res = cost.solve(Irls::new(), DVector::from_row_slice(&[0.5, 2.0]));
+        let alpha = &res.state.best_param;
+        let c = res.operator.problem().linear_coefficients(alpha).unwrap();
+
+        assert!((alpha - DVector::from_row_slice(&[1.0, 3.0])).norm() < 1E-3);
+        assert!((c - DVector::from_row_slice(&[2.0, -1.0])).norm() < 1E-3);
+    }
+}
@@ -1,5 +1,12 @@
+pub mod constrained;
+pub mod derivativefree;
+pub mod equations;
+pub mod global;
+pub mod leastsquares;
 pub mod linesearch;
 pub mod newton;
+pub mod projected_gradient;
+pub mod qp;
 pub mod quasinewton;
 pub mod steepest_descent;
-pub mod trustregion;
\ No newline at end of file
+pub mod trustregion;
@@ -6,7 +6,17 @@ static TOLERANCE : f64 = 1E-5;
 
 /// Cholesky LDL factorization, for the moment without modifications
 // (Algorithm 3.4 at page 53).
-pub fn factorization(mat_a: &DMatrix<f64>, delta: f64, beta: f64) -> Result<(DMatrix<f64>, DVector<f64>), Error>
+///
+/// min_beta is only a lower bound of β, which is raised to the bound
+/// of Gill, Murray and Wright, β² >= max(γ, ξ / √(n² - 1)), where γ and ξ
+/// are the largest diagonal and off-diagonal entries of A, so that
+/// a positive definite matrix is never modified, however badly scaled it is.
+///
+/// Reference:
+///
+/// Philip E. Gill, Walter Murray and Margaret H. Wright (1981).
+/// Practical Optimization. [chapter 4.4.2]
+pub fn factorization(mat_a: &DMatrix<f64>, delta: f64, min_beta: f64) -> Result<(DMatrix<f64>, DVector<f64>), Error>
 {
     let (rows, cols) = mat_a.shape();
 
@@ -14,6 +24,14 @@ pub fn factorization(mat_a: &DMatrix<f64>, delta: f64, beta: f64) -> Result<(DMa
         return Err(Error::msg("Unable to compute the Cholesky decomposition: the input matrix is not square."))
     }
 
+    let gamma = (0 .. rows).map(|i| mat_a[(i, i)].abs()).fold(0.0, f64::max);
+    let xi = (0 .. rows)
+        .flat_map(|i| (0 .. i).map(move |j| (i, j)))
+        .map(|(i, j)| mat_a[(i, j)].abs())
+        .fold(0.0, f64::max);
+    let nu = ((rows * rows) as f64 - 1.0).sqrt().max(1.0);
+    let beta = min_beta.max(gamma.sqrt()).max((xi / nu).sqrt());
+
     let mut mat_l = DMatrix::<f64>::identity(rows, cols);
     let mut mat_d = DVector::<f64>::repeat(rows, 0.0);
 
@@ -30,10 +48,10 @@ pub fn factorization(mat_a: &DMatrix<f64>, delta: f64, beta: f64) -> Result<(DMa
                 mat_a[(i,j)] - (0 .. j).map(|s| mat_d[s] * mat_l[(i,s)] * mat_l[(j,s)]).sum::<f64>();
             mat_c[(i, j)] = c_ij;
 
-            theta_j = if c_ij > theta_j { c_ij } else { theta_j };
+            theta_j = if c_ij.abs() > theta_j { c_ij.abs() } else { theta_j };
         }
 
-        // d_j = max(|c_ij|, (theta_j / beta)^2, delta)
+        // d_j = max(|c_jj|, (theta_j / beta)^2, delta)
         let mut d_j =  mat_c[(j, j)].abs();
         if theta_j > f64::NEG_INFINITY && d_j < (theta_j / beta).powi(2) {
             d_j = (theta_j / beta).powi(2);
@@ -111,6 +129,21 @@ mod tests {
         assert!(is_positive_definite(&corrected));
     }
 
+    #[test]
+    fn test_badly_scaled() {
+        // positive definite, with off-diagonal entries much larger than beta
+        let mat_a = DMatrix::from_row_slice(2, 2, &[
+            1E6, 9E5,
+            9E5, 1E6
+        ]);
+
+        let (mat_l, vec_d) = factorization(&mat_a, 1E-4, 10.0).unwrap();
+        let mat_d = DMatrix::from_diagonal(&vec_d);
+
+        let check = &mat_l * mat_d * mat_l.transpose();
+        assert!((check - &mat_a).norm() < 1E-10 * mat_a.norm());
+    }
+
     #[test]
     fn test_solve() {
         let mat_a = DMatrix::from_row_slice(3, 3, &[
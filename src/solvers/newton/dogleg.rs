@@ -24,6 +24,50 @@ fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)>
     }
 }
 
+/// Dogleg step for the quadratic model m(p) = gᵀp + ½ pᵀBp,
+/// combining the Cauchy point p_U with the full step p_B
+/// inside the trust region ‖p‖ <= delta.
+/// The full step is computed only when the Cauchy point is
+/// inside the trust region.
+/// (Algorithm 4.3 at page 73)
+pub fn dogleg_step<F>(grad: &DVector<f64>, hessian: &DMatrix<f64>, full_step: F, delta: f64)
+    -> Result<DVector<f64>, Error>
+where
+    F: FnOnce() -> Result<DVector<f64>, Error>
+{
+    let grad_norm = grad.norm();
+    // the model has no descent direction, and the Cauchy point is p = 0
+    if grad_norm == 0.0 {
+        return Ok(DVector::zeros(grad.len()));
+    }
+
+    let curvature = (grad.transpose() * (hessian * grad))[(0, 0)];
+
+    // Along the steepest descent direction the model is unbounded
+    // or its minimum is outside the trust region.
+    if curvature <= 0.0 || grad_norm.powi(3) / curvature >= delta {
+        return Ok(-(delta / grad_norm) * grad);
+    }
+
+    let param_u = -(grad_norm.powi(2) / curvature) * grad;
+
+    let param_b = full_step()?;
+    if param_b.norm() <= delta {
+        return Ok(param_b);
+    }
+
+    let p_bu = &param_b - &param_u;
+
+    // nu == tau - 1
+    let (_, nu) = solve_quadratic(
+        p_bu.norm_squared(),
+        2.0 * param_u.dot(&p_bu),
+        &param_u.norm_squared() - delta.powi(2)
+    ).ok_or(Error::msg("Cannot compute step length."))?;
+
+    Ok(&param_u + nu * &p_bu)
+}
+
 #[derive(Serialize, Deserialize, Solverf64)]
 pub struct NewtonDogleg{
     delta : f64
@@ -54,33 +98,10 @@ where
             .as_ref()
             .ok_or(Error::msg("hessian not available."))?;
 
-        let u_factor = grad.norm() / (grad.transpose() * (hessian * grad))[(0, 0)];
-        let param_u = -u_factor * grad;
-
-        if param_u.norm() > delta {
-            let tau = ((grad.transpose() * (hessian * grad))[(0, 0)] * delta) / grad.norm();
-            Ok(tau * param_u)
-        }
-        else {
+        dogleg_step(grad, hessian, || {
             let (mat_l, vec_d) = cholesky::factorization(&hessian, CHOL_DELTA, CHOL_BETA)?;
-            let param_b = cholesky::solve(&mat_l, &vec_d, &(-grad))?;
-
-            if param_b.norm() > delta {
-                let p_bu = &param_b - &param_u;
-    
-                // nu == tau - 1
-                let (_, nu) = solve_quadratic(
-                    p_bu.norm_squared(),
-                    param_u.dot(&p_bu),
-                    &param_u.norm_squared() - delta.powi(2)
-                ).ok_or(Error::msg("Cannot compute step length."))?;
-    
-                Ok(&param_u + nu * &p_bu)
-            }
-            else {
-                Ok(param_b)
-            }
-        }
+            cholesky::solve(&mat_l, &vec_d, &(-grad))
+        }, delta)
     }
 
     fn subproblem(&self, state: &IterState<O>, param: &O::Param)
@@ -99,3 +120,65 @@ where
         Ok(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::quadratic::Quadratic;
+    use crate::functions::rosenbrock::Rosenbrock2D;
+
+    #[test]
+    fn test_quadratic() {
+        // the minimum (1000, 10) is far outside the initial trust region,
+        // so the first steps are Cauchy points and dogleg steps
+        let func = Quadratic::new(
+            DMatrix::from_row_slice(2, 2, &[
+                1f64, 0f64,
+                0f64, 100f64
+            ]),
+            DVector::from_row_slice(&[-1000.0, -1000.0]),
+            0.0,
+        );
+
+        let res = Executor::new(func, NewtonDogleg::new(10.0), DVector::zeros(2))
+            .max_iters(15)
+            .run()
+            .unwrap();
+
+        assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+        assert!((&res.state.best_param - DVector::from_row_slice(&[1000.0, 10.0])).norm() < 1E-5);
+    }
+
+    #[test]
+    fn test_initial_radius() {
+        let func = Quadratic::new(
+            DMatrix::from_row_slice(2, 2, &[
+                1f64, 0f64,
+                0f64, 100f64
+            ]),
+            DVector::from_row_slice(&[-1000.0, -1000.0]),
+            0.0,
+        );
+
+        // the first step stays inside the initial trust region
+        let res = Executor::new(func, NewtonDogleg::new(1.0), DVector::zeros(2))
+            .max_iters(1)
+            .run()
+            .unwrap();
+
+        assert!(res.state.best_param.norm() <= 1.0 + 1E-10);
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let func = Rosenbrock2D::new(1.0, 100.0);
+
+        let res = Executor::new(func, NewtonDogleg::new(10.0), DVector::from_row_slice(&[-1.2, 1.0]))
+            .max_iters(100)
+            .run()
+            .unwrap();
+
+        assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+        assert!((&res.state.best_param - DVector::from_row_slice(&[1.0, 1.0])).norm() < 1E-4);
+    }
+}
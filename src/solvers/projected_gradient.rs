@@ -0,0 +1,225 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::functions::bounds::Bounds;
+use crate::solvers::linesearch::LineSearch;
+use crate::solvers::steepest_descent::SteepestDescent;
+use crate::steplength::backtracking;
+
+static TOLERANCE : f64 = 1E-5;
+
+/// Safeguards for the Barzilai-Borwein step length.
+static MIN_STEP : f64 = 1E-10;
+static MAX_STEP : f64 = 1E10;
+
+/// Projected steepest descent for box-constrained problems.
+///
+/// The steepest descent direction, with the components of the active set
+/// removed, is followed along the projection arc P(x + α d) onto the bounds,
+/// with backtracking until the Armijo condition holds.
+/// With the Barzilai-Borwein variant the first trial step is sᵀs / sᵀy,
+/// from the last step s and the last change y of the gradient.
+/// The solver terminates when the projected gradient P(x - ∇f(x)) - x
+/// is smaller than the tolerance.
+///
+/// References:
+///
+/// Dimitri P. Bertsekas (1999). Nonlinear Programming. [chapter 2.3]
+///
+/// Ernesto G. Birgin, José Mario Martínez and Marcos Raydan (2000).
+/// Nonmonotone Spectral Projected Gradient Methods on Convex Sets.
+#[derive(Serialize, Deserialize)]
+pub struct ProjectedGradient {
+    bounds: Bounds,
+    barzilai_borwein: bool,
+    tolerance: f64,
+    steepest_descent: SteepestDescent,
+    // first trial step of the next iteration
+    step_length: f64,
+}
+
+impl ProjectedGradient {
+    pub fn new(bounds: Bounds) -> Self {
+        ProjectedGradient {
+            bounds,
+            barzilai_borwein: false,
+            tolerance: TOLERANCE,
+            steepest_descent: SteepestDescent::new(),
+            step_length: 1.0,
+        }
+    }
+
+    pub fn with_barzilai_borwein(mut self) -> Self {
+        self.barzilai_borwein = true;
+        self
+    }
+
+    /// Terminate when the largest component of the projected gradient
+    /// is smaller than tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl<O> Solver<O> for ProjectedGradient
+where
+    O: ArgminOp<
+        Output = f64,
+        Float = f64,
+        Param = DVector<f64>,
+        Hessian = DMatrix<f64>
+    >
+{
+    const NAME: &'static str = "ProjectedGradient";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        if param.len() != self.bounds.dimension() {
+            return Err(Error::msg("The initial point and the bounds have different dimensions."));
+        }
+
+        let param = self.bounds.project(&param);
+        let cost = op.apply(&param)?;
+        let grad = op.gradient(&param)?;
+        self.step_length = 1.0;
+
+        Ok(Some(ArgminIterData::new().param(param).cost(cost).grad(grad)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = &state.param;
+        let grad = state.grad
+            .as_ref()
+            .ok_or(Error::msg("gradient unavailable"))?;
+
+        let mut descent_dir = self.steepest_descent.descent_dir(op, state)?;
+        for i in self.bounds.active_set(param, grad) {
+            descent_dir[i] = 0.0;
+        }
+
+        let initial_step_length = if self.barzilai_borwein { self.step_length } else { 1.0 };
+        let step_length = backtracking::projected_search(
+            &*op,
+            &self.bounds,
+            param,
+            state.cost,
+            grad,
+            &descent_dir,
+            initial_step_length
+        )?;
+
+        let next_param = self.bounds.project(&(param + step_length * &descent_dir));
+        let next_cost = op.apply(&next_param)?;
+        let next_grad = op.gradient(&next_param)?;
+
+        let s = &next_param - param;
+        let y = &next_grad - grad;
+        let curvature = s.dot(&y);
+        self.step_length = if curvature > 0.0 {
+            (s.norm_squared() / curvature).max(MIN_STEP).min(MAX_STEP)
+        }
+        else {
+            1.0
+        };
+
+        Ok(ArgminIterData::new().param(next_param).cost(next_cost).grad(next_grad))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason
+    {
+        if let Some(grad) = state.grad.as_ref() {
+            if self.bounds.projected_gradient(&state.param, grad).amax() <= self.tolerance {
+                TerminationReason::TargetPrecisionReached
+            }
+            else {
+                TerminationReason::NotTerminated
+            }
+        }
+        else {
+            TerminationReason::Aborted
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::Function;
+    use crate::functions::quadratic::Quadratic;
+    use crate::functions::rosenbrock::Rosenbrock2D;
+
+    #[test]
+    fn test_quadratic() {
+        let func = Quadratic::new(
+            DMatrix::from_row_slice(3, 3, &[
+                2f64, 1f64, 0f64,
+                1f64, 2f64, 0f64,
+                0f64, 0f64, 1f64
+            ]),
+            DVector::from_row_slice(&[0.0, 1.0, 2.0]),
+            0.0,
+        );
+        let bounds = Bounds::new(
+            DVector::from_row_slice(&[-1.0, 0.0, -1.0]),
+            DVector::from_row_slice(&[1.0, 1.0, 1.0]),
+        ).unwrap();
+        let x0 = DVector::from_row_slice(&[0.5, 0.5, 0.5]);
+
+        let res = func.solve(ProjectedGradient::new(bounds.clone()), x0);
+
+        assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+        let expected = DVector::from_row_slice(&[0.0, 0.0, -1.0]);
+        assert!((&res.state.param - &expected).norm() < 1E-5);
+
+        let grad = res.state.grad.unwrap();
+        assert_eq!(vec![1, 2], bounds.active_set(&res.state.param, &grad));
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let bounds = Bounds::new(
+            DVector::from_row_slice(&[-2.0, -1.0]),
+            DVector::from_row_slice(&[0.5, 2.0]),
+        ).unwrap();
+
+        let solvers = vec![
+            ProjectedGradient::new(bounds.clone()),
+            ProjectedGradient::new(bounds).with_barzilai_borwein(),
+        ];
+
+        for solver in solvers {
+            let x0 = DVector::from_row_slice(&[-1.2, 1.0]);
+            let res = Executor::new(Rosenbrock2D::new(1.0, 100.0), solver, x0)
+                .max_iters(1000)
+                .run()
+                .unwrap();
+
+            assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+            assert!((&res.state.param - DVector::from_row_slice(&[0.5, 0.25])).norm() < 1E-5);
+        }
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let bounds = Bounds::new(
+            DVector::from_row_slice(&[-2.0, -1.0]),
+            DVector::from_row_slice(&[0.5, 2.0]),
+        ).unwrap();
+        let x0 = DVector::from_row_slice(&[-1.2, 1.0, 0.0]);
+
+        assert!(Executor::new(Rosenbrock2D::new(1.0, 100.0), ProjectedGradient::new(bounds), x0)
+            .max_iters(10)
+            .run()
+            .is_err());
+    }
+}
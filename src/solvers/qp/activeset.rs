@@ -0,0 +1,407 @@
+use argmin::prelude::Error;
+use nalgebra::{DMatrix, DVector};
+
+use crate::functions::constraints::{ConstrainedProblem, LinearConstraints};
+use crate::functions::quadratic::Quadratic;
+use crate::solvers::qp::{solve_equality, KktMethod};
+
+static TOLERANCE : f64 = 1E-9;
+static MAX_ITERS : usize = 1000;
+
+/// Penalty on the infeasibility in the first phase,
+/// increased tenfold until the infeasibility vanishes.
+static INITIAL_PENALTY : f64 = 1.0;
+static MAX_PENALTY : f64 = 1E10;
+
+/// Solution of a quadratic program with inequality constraints,
+/// such that Qx + b + A_Eᵀλ_E + A_Iᵀλ_I = 0 and λ_I >= 0.
+#[derive(Clone, Debug)]
+pub struct ActiveSetSolution {
+    pub param: DVector<f64>,
+    pub cost: f64,
+    pub equality_multipliers: DVector<f64>,
+    /// Zero for the constraints outside the active set.
+    pub inequality_multipliers: DVector<f64>,
+    /// Indices of the inequality constraints in the final working set.
+    pub active_set: Vec<usize>,
+    pub iterations: usize,
+}
+
+/// Linear constraints, with the inequalities in the working set.
+struct WorkingSet<'a> {
+    mat_e: &'a DMatrix<f64>,
+    rhs_e: &'a DVector<f64>,
+    mat_i: &'a DMatrix<f64>,
+    rhs_i: &'a DVector<f64>,
+    working: Vec<usize>,
+}
+
+/// Primal active-set method for convex quadratic programs
+///
+///  min ½xᵀQx + bᵀx + c
+///  subject to A_E x = d_E, A_I x <= d_I,
+///
+/// where Q is positive definite on the null space of every working set,
+/// e.g. positive definite, and the inequalities are those of a ConstrainedProblem,
+/// [A_I x - b_I; l - x; x - u] <= 0, in this order.
+///
+/// Every iteration solves the equality-constrained problem where the
+/// constraints of the working set hold with equality.
+/// A nonzero step is followed until the first blocking constraint,
+/// which joins the working set; at a zero step the constraint with the most
+/// negative multiplier leaves it, unless all of them are nonnegative and
+/// the point is optimal.
+/// After a degenerate step of length zero Bland's rule is used instead,
+/// which picks the lowest index both for the constraint that leaves and,
+/// among ties, for the blocking constraint, so that the method cannot cycle.
+///
+/// When the starting point is not feasible, the first phase solves
+///
+///  min ½|x - x_0|² + ½η² + Mη
+///  subject to -η <= A_E x - d_E <= η, A_I x - d_I <= η, η >= 0,
+///
+/// starting from the feasible point (x_0, max violation),
+/// and increases the penalty M until η vanishes.
+///
+/// Reference:
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization. [chapter 16.5]
+pub struct ActiveSet {
+    tolerance: f64,
+    max_iters: usize,
+}
+
+impl ActiveSet {
+    pub fn new() -> Self {
+        ActiveSet {
+            tolerance: TOLERANCE,
+            max_iters: MAX_ITERS,
+        }
+    }
+
+    /// Tolerance on the steps, the multipliers and the feasibility.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Maximum number of iterations of each phase.
+    pub fn with_max_iters(mut self, max_iters: usize) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    pub fn solve(&self, problem: &ConstrainedProblem<Quadratic>, param: DVector<f64>)
+        -> Result<ActiveSetSolution, Error>
+    {
+        let quadratic = problem.function();
+        let n = quadratic.b().len();
+        if param.len() != n {
+            return Err(Error::msg("The starting point doesn't match the dimension of the quadratic function."));
+        }
+
+        let (equalities, inequalities) = problem.linear_constraints(n)?;
+        let (mat_e, rhs_e) = (equalities.mat(), equalities.rhs());
+        let (mat_i, rhs_i) = (inequalities.mat(), inequalities.rhs());
+
+        let (param, phase_one_iterations) = self.feasible_point(mat_e, rhs_e, mat_i, rhs_i, param)?;
+
+        let mut constraints = WorkingSet { mat_e, rhs_e, mat_i, rhs_i, working: Vec::new() };
+        constraints.working = constraints.active(&param, self.tolerance);
+
+        let (param, lambda_e, lambda_w, iterations) =
+            self.minimize(quadratic.mat_q(), quadratic.b(), &mut constraints, param)?;
+
+        let mut inequality_multipliers = DVector::zeros(mat_i.nrows());
+        for (k, &i) in constraints.working.iter().enumerate() {
+            inequality_multipliers[i] = lambda_w[k];
+        }
+        let mut active_set = constraints.working;
+        active_set.sort();
+
+        Ok(ActiveSetSolution {
+            cost: quadratic.evaluate_at(&param),
+            param,
+            equality_multipliers: lambda_e,
+            inequality_multipliers,
+            active_set,
+            iterations: phase_one_iterations + iterations,
+        })
+    }
+
+    /// First phase: a feasible point, with the number of iterations needed.
+    fn feasible_point(
+        &self,
+        mat_e: &DMatrix<f64>,
+        rhs_e: &DVector<f64>,
+        mat_i: &DMatrix<f64>,
+        rhs_i: &DVector<f64>,
+        param: DVector<f64>
+    ) -> Result<(DVector<f64>, usize), Error> {
+        let n = param.len();
+        let infeasibility = |param: &DVector<f64>| {
+            let equality = (mat_e * param - rhs_e).iter().fold(0.0, |max, c| c.abs().max(max));
+            let inequality = (mat_i * param - rhs_i).iter().cloned().fold(0.0, f64::max);
+            equality.max(inequality)
+        };
+
+        let violation = infeasibility(&param);
+        if violation <= self.tolerance {
+            return Ok((param, 0));
+        }
+
+        // constraints on (x, η): [A_E -1; -A_E -1; A_I -1; 0 -1] (x, η) <= [d_E; -d_E; d_I; 0]
+        let (m_e, m_i) = (mat_e.nrows(), mat_i.nrows());
+        let rows = 2 * m_e + m_i + 1;
+        let mut mat = DMatrix::zeros(rows, n + 1);
+        let mut rhs = DVector::zeros(rows);
+        mat.slice_mut((0, 0), (m_e, n)).copy_from(mat_e);
+        rhs.rows_mut(0, m_e).copy_from(rhs_e);
+        mat.slice_mut((m_e, 0), (m_e, n)).copy_from(&(-mat_e));
+        rhs.rows_mut(m_e, m_e).copy_from(&(-rhs_e));
+        mat.slice_mut((2 * m_e, 0), (m_i, n)).copy_from(mat_i);
+        rhs.rows_mut(2 * m_e, m_i).copy_from(rhs_i);
+        mat.column_mut(n).fill(-1.0);
+
+        let no_equalities = (DMatrix::zeros(0, n + 1), DVector::zeros(0));
+        let mut constraints = WorkingSet {
+            mat_e: &no_equalities.0,
+            rhs_e: &no_equalities.1,
+            mat_i: &mat,
+            rhs_i: &rhs,
+            working: Vec::new(),
+        };
+
+        let mat_q = DMatrix::identity(n + 1, n + 1);
+        let mut b = DVector::zeros(n + 1);
+        b.rows_mut(0, n).copy_from(&(-&param));
+
+        let mut extended = param.clone().insert_row(n, violation);
+        let mut iterations = 0;
+        let mut penalty = INITIAL_PENALTY;
+        while penalty <= MAX_PENALTY {
+            b[n] = penalty;
+            constraints.working = constraints.active(&extended, self.tolerance);
+
+            let (next, _, _, iters) = self.minimize(&mat_q, &b, &mut constraints, extended)?;
+            extended = next;
+            iterations += iters;
+
+            let candidate = extended.rows(0, n).clone_owned();
+            if infeasibility(&candidate) <= self.tolerance {
+                return Ok((candidate, iterations));
+            }
+            penalty *= 10.0;
+        }
+
+        Err(Error::msg("The quadratic program is infeasible."))
+    }
+
+    /// Second phase from a feasible point: the solution, the multipliers of the
+    /// equalities and of the working set, and the number of iterations.
+    fn minimize(
+        &self,
+        mat_q: &DMatrix<f64>,
+        b: &DVector<f64>,
+        constraints: &mut WorkingSet,
+        mut param: DVector<f64>
+    ) -> Result<(DVector<f64>, DVector<f64>, DVector<f64>, usize), Error> {
+        let m_e = constraints.mat_e.nrows();
+        let mut degenerate = false;
+
+        for iter in 0 .. self.max_iters {
+            // min ½pᵀQp + gᵀp subject to a_iᵀp = 0 for the working set
+            let grad = mat_q * &param + b;
+            let subproblem = Quadratic::new(mat_q.clone(), grad, 0.0);
+            let mat_w = constraints.working_matrix();
+            let homogeneous = LinearConstraints::new(mat_w.clone(), DVector::zeros(mat_w.nrows()))?;
+            let step = solve_equality(&subproblem, &homogeneous, KktMethod::NullSpace)?;
+
+            if step.param.norm() <= self.tolerance * (1.0 + param.norm()) {
+                let lambda_w = step.multipliers.rows(m_e, constraints.working.len()).clone_owned();
+
+                let negative = (0 .. constraints.working.len())
+                    .filter(|&k| lambda_w[k] < -self.tolerance);
+                let leaving = if degenerate {
+                    negative.min_by_key(|&k| constraints.working[k])
+                }
+                else {
+                    negative.min_by(|&k, &l| lambda_w[k].partial_cmp(&lambda_w[l]).unwrap())
+                };
+
+                match leaving {
+                    Some(k) => {
+                        constraints.working.remove(k);
+                    },
+                    None => {
+                        let lambda_e = step.multipliers.rows(0, m_e).clone_owned();
+                        return Ok((param, lambda_e, lambda_w, iter));
+                    }
+                }
+            }
+            else {
+                let (step_length, blocking) = constraints.step_length(&param, &step.param, self.tolerance);
+                param += step_length * &step.param;
+                degenerate = step_length <= self.tolerance;
+
+                if let Some(i) = blocking {
+                    constraints.working.push(i);
+                }
+            }
+        }
+
+        Err(Error::msg("The active-set method reached the maximum number of iterations."))
+    }
+}
+
+impl<'a> WorkingSet<'a> {
+    /// The equalities, then the inequalities of the working set.
+    fn working_matrix(&self) -> DMatrix<f64> {
+        let n = self.mat_e.ncols();
+        let m_e = self.mat_e.nrows();
+
+        let mut mat = DMatrix::zeros(m_e + self.working.len(), n);
+        mat.rows_mut(0, m_e).copy_from(self.mat_e);
+        for (k, &i) in self.working.iter().enumerate() {
+            mat.row_mut(m_e + k).copy_from(&self.mat_i.row(i));
+        }
+        mat
+    }
+
+    /// Active inequalities at a feasible point,
+    /// skipping those that would make the working set linearly dependent.
+    fn active(&self, param: &DVector<f64>, tolerance: f64) -> Vec<usize> {
+        let n = param.len();
+        let residual = self.mat_i * param - self.rhs_i;
+
+        let mut working = Vec::new();
+        let mut mat = self.mat_e.clone();
+        for i in 0 .. self.mat_i.nrows() {
+            if residual[i].abs() > tolerance {
+                continue;
+            }
+
+            let rows = mat.nrows();
+            let mut candidate = mat.clone().insert_row(rows, 0.0);
+            candidate.row_mut(rows).copy_from(&self.mat_i.row(i));
+
+            if rows < n && candidate.clone().svd(false, false).rank(tolerance) == rows + 1 {
+                mat = candidate;
+                working.push(i);
+            }
+        }
+        working
+    }
+
+    /// Largest α <= 1 such that x + α p is feasible, with the blocking
+    /// constraint of lowest index if α < 1.
+    fn step_length(&self, param: &DVector<f64>, step: &DVector<f64>, tolerance: f64) -> (f64, Option<usize>) {
+        let mut step_length = 1.0;
+        let mut blocking = None;
+
+        for i in 0 .. self.mat_i.nrows() {
+            if self.working.contains(&i) {
+                continue;
+            }
+
+            let slope = self.mat_i.row(i).dot(&step.transpose());
+            if slope <= tolerance {
+                continue;
+            }
+
+            let ratio = ((self.rhs_i[i] - self.mat_i.row(i).dot(&param.transpose())) / slope).max(0.0);
+            if ratio < step_length - tolerance || (blocking.is_none() && ratio <= step_length) {
+                step_length = ratio;
+                blocking = Some(i);
+            }
+        }
+
+        (step_length, blocking)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::bounds::Bounds;
+
+    #[test]
+    fn test_active_set() {
+        // example 16.4 of Nocedal and Wright
+        let quadratic = Quadratic::new(
+            DMatrix::from_diagonal(&DVector::from_row_slice(&[2.0, 2.0])),
+            DVector::from_row_slice(&[-2.0, -5.0]),
+            7.25,
+        );
+        let constraints = LinearConstraints::new(
+            DMatrix::from_row_slice(3, 2, &[
+                -1f64, 2f64,
+                1f64, 2f64,
+                1f64, -2f64
+            ]),
+            DVector::from_row_slice(&[2.0, 6.0, 2.0]),
+        ).unwrap();
+        let bounds = Bounds::new(DVector::zeros(2), DVector::from_element(2, f64::INFINITY)).unwrap();
+        let problem = ConstrainedProblem::new(quadratic)
+            .with_linear_inequalities(constraints)
+            .with_bounds(bounds);
+
+        // feasible starting point, and infeasible starting point
+        for &x0 in [[2.0, 0.0], [5.0, 5.0]].iter() {
+            let solution = ActiveSet::new().solve(&problem, DVector::from_row_slice(&x0)).unwrap();
+
+            assert!((&solution.param - DVector::from_row_slice(&[1.4, 1.7])).norm() < 1E-8);
+            assert_eq!(vec![0], solution.active_set);
+            assert!((solution.inequality_multipliers[0] - 0.8).abs() < 1E-8);
+            assert!(solution.inequality_multipliers.iter().all(|&lambda| lambda >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // three constraints active at the solution (0, 0) in the plane,
+        // and an equality
+        let quadratic = Quadratic::new(
+            DMatrix::identity(3, 3),
+            DVector::from_row_slice(&[1.0, 1.0, -1.0]),
+            0.0,
+        );
+        let equalities = LinearConstraints::new(
+            DMatrix::from_row_slice(1, 3, &[0f64, 0f64, 1f64]),
+            DVector::from_row_slice(&[0.5]),
+        ).unwrap();
+        let inequalities = LinearConstraints::new(
+            DMatrix::from_row_slice(3, 3, &[
+                -1f64, 0f64, 0f64,
+                0f64, -1f64, 0f64,
+                -1f64, -1f64, 0f64
+            ]),
+            DVector::zeros(3),
+        ).unwrap();
+        let problem = ConstrainedProblem::new(quadratic)
+            .with_linear_equalities(equalities)
+            .with_linear_inequalities(inequalities);
+
+        let solution = ActiveSet::new().solve(&problem, DVector::from_row_slice(&[1.0, 1.0, 0.5])).unwrap();
+
+        assert!((&solution.param - DVector::from_row_slice(&[0.0, 0.0, 0.5])).norm() < 1E-8);
+        assert!((solution.equality_multipliers[0] - 0.5).abs() < 1E-8);
+        let residual = DVector::from_row_slice(&[1.0, 1.0, -0.5])
+            + problem.equality_jacobian(&solution.param).unwrap().transpose() * &solution.equality_multipliers
+            + problem.inequality_jacobian(&solution.param).unwrap().transpose() * &solution.inequality_multipliers;
+        assert!(residual.norm() < 1E-8);
+        assert!(solution.inequality_multipliers.iter().all(|&lambda| lambda >= 0.0));
+    }
+
+    #[test]
+    fn test_infeasible() {
+        let quadratic = Quadratic::new(DMatrix::identity(1, 1), DVector::zeros(1), 0.0);
+        let inequalities = LinearConstraints::new(
+            DMatrix::from_row_slice(2, 1, &[1f64, -1f64]),
+            DVector::from_row_slice(&[-1.0, -1.0]),
+        ).unwrap();
+        let problem = ConstrainedProblem::new(quadratic).with_linear_inequalities(inequalities);
+
+        assert!(ActiveSet::new().solve(&problem, DVector::zeros(1)).is_err());
+    }
+}
@@ -0,0 +1,246 @@
+use argmin::prelude::Error;
+use nalgebra::{DMatrix, DVector};
+
+/// Pivots below this value make the matrix singular.
+static TOLERANCE : f64 = 1E-12;
+
+/// Factorization Pᵀ A P = L D Lᵀ of a symmetric, possibly indefinite matrix,
+/// where L is unit lower triangular and D is block diagonal with blocks
+/// of size 1 or 2.
+/// The pivots are chosen with the partial pivoting strategy of Bunch and Kaufman,
+/// which bounds the growth of the entries of L.
+///
+/// References:
+///
+/// James R. Bunch and Linda Kaufman (1977). Some Stable Methods for
+/// Calculating Inertia and Solving Symmetric Linear Systems.
+///
+/// Gene H. Golub and Charles F. Van Loan (2013). Matrix Computations. [chapter 4.4]
+#[derive(Clone, Debug)]
+pub struct Factorization {
+    mat_l: DMatrix<f64>,
+    mat_d: DMatrix<f64>,
+    // row i of Pᵀ A P is row permutation[i] of A
+    permutation: Vec<usize>,
+    // sizes of the diagonal blocks of D
+    blocks: Vec<usize>,
+}
+
+pub fn factorization(mat_a: &DMatrix<f64>) -> Result<Factorization, Error> {
+    let (n, cols) = mat_a.shape();
+    if n != cols {
+        return Err(Error::msg("Unable to compute the Bunch-Kaufman factorization: the input matrix is not square."));
+    }
+
+    let alpha = (1.0 + 17f64.sqrt()) / 8.0;
+
+    let mut mat = mat_a.clone();
+    let mut mat_l = DMatrix::identity(n, n);
+    let mut mat_d = DMatrix::zeros(n, n);
+    let mut permutation: Vec<usize> = (0 .. n).collect();
+    let mut blocks = Vec::new();
+
+    let mut k = 0;
+    while k < n {
+        // largest off-diagonal entry in column k
+        let (mut r, mut lambda) = (k, 0.0);
+        for i in k + 1 .. n {
+            if mat[(i, k)].abs() > lambda {
+                r = i;
+                lambda = mat[(i, k)].abs();
+            }
+        }
+
+        let size = if lambda == 0.0 || mat[(k, k)].abs() >= alpha * lambda {
+            1
+        }
+        else {
+            let sigma = (k .. n)
+                .filter(|&j| j != r)
+                .map(|j| mat[(j, r)].abs())
+                .fold(0.0, f64::max);
+
+            if mat[(k, k)].abs() * sigma >= alpha * lambda * lambda {
+                1
+            }
+            else if mat[(r, r)].abs() >= alpha * sigma {
+                interchange(&mut mat, &mut mat_l, &mut permutation, k, k, r);
+                1
+            }
+            else {
+                interchange(&mut mat, &mut mat_l, &mut permutation, k, k + 1, r);
+                2
+            }
+        };
+
+        let pivot = mat.slice((k, k), (size, size)).clone_owned();
+        if pivot.determinant().abs() <= TOLERANCE {
+            return Err(Error::msg("Unable to compute the Bunch-Kaufman factorization: the input matrix is singular."));
+        }
+        let pivot_inv = pivot.clone().try_inverse()
+            .ok_or(Error::msg("Unable to compute the Bunch-Kaufman factorization: the input matrix is singular."))?;
+        mat_d.slice_mut((k, k), (size, size)).copy_from(&pivot);
+
+        let rest = n - k - size;
+        if rest > 0 {
+            let column = mat.slice((k + size, k), (rest, size)).clone_owned();
+            let l_block = &column * &pivot_inv;
+            let update = &l_block * column.transpose();
+
+            let mut trailing = mat.slice_mut((k + size, k + size), (rest, rest));
+            trailing -= update;
+            mat_l.slice_mut((k + size, k), (rest, size)).copy_from(&l_block);
+        }
+
+        blocks.push(size);
+        k += size;
+    }
+
+    Ok(Factorization { mat_l, mat_d, permutation, blocks })
+}
+
+/// Symmetric interchange of rows and columns i and j >= i of the trailing
+/// submatrix, starting at k, together with the rows of the first k columns of L.
+fn interchange(
+    mat: &mut DMatrix<f64>,
+    mat_l: &mut DMatrix<f64>,
+    permutation: &mut Vec<usize>,
+    k: usize,
+    i: usize,
+    j: usize
+) {
+    if i == j {
+        return;
+    }
+
+    mat.swap_rows(i, j);
+    mat.swap_columns(i, j);
+    for col in 0 .. k {
+        mat_l.swap((i, col), (j, col));
+    }
+    permutation.swap(i, j);
+}
+
+impl Factorization {
+    pub fn mat_l(&self) -> &DMatrix<f64> {
+        &self.mat_l
+    }
+
+    pub fn mat_d(&self) -> &DMatrix<f64> {
+        &self.mat_d
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// Numbers of positive, negative and zero eigenvalues,
+    /// which are the same for A and D by Sylvester's law of inertia.
+    pub fn inertia(&self) -> (usize, usize, usize) {
+        let (mut positive, mut negative, mut zero) = (0, 0, 0);
+
+        let mut k = 0;
+        for &size in &self.blocks {
+            if size == 1 {
+                let d = self.mat_d[(k, k)];
+                if d > TOLERANCE {
+                    positive += 1;
+                }
+                else if d < -TOLERANCE {
+                    negative += 1;
+                }
+                else {
+                    zero += 1;
+                }
+            }
+            else {
+                // 2x2 pivots have a negative determinant,
+                // hence eigenvalues of opposite signs
+                positive += 1;
+                negative += 1;
+            }
+            k += size;
+        }
+
+        (positive, negative, zero)
+    }
+
+    /// Solve A x = b.
+    pub fn solve(&self, b: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        let n = self.permutation.len();
+        if b.len() != n {
+            return Err(Error::msg("Cannot solve the Bunch-Kaufman system: incompatible dimensions."));
+        }
+
+        let rhs = DVector::from_fn(n, |i, _| b[self.permutation[i]]);
+        let mut y = self.mat_l.solve_lower_triangular(&rhs)
+            .ok_or(Error::msg("Cannot solve the Bunch-Kaufman system."))?;
+
+        let mut k = 0;
+        for &size in &self.blocks {
+            let block = self.mat_d.slice((k, k), (size, size)).clone_owned();
+            let z = block.lu().solve(&y.rows(k, size).clone_owned())
+                .ok_or(Error::msg("Cannot solve the Bunch-Kaufman system."))?;
+            y.rows_mut(k, size).copy_from(&z);
+            k += size;
+        }
+
+        let w = self.mat_l.transpose().solve_upper_triangular(&y)
+            .ok_or(Error::msg("Cannot solve the Bunch-Kaufman system."))?;
+
+        let mut x = DVector::zeros(n);
+        for i in 0 .. n {
+            x[self.permutation[i]] = w[i];
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reconstruct(fact: &Factorization) -> DMatrix<f64> {
+        let mat = fact.mat_l() * fact.mat_d() * fact.mat_l().transpose();
+        let n = mat.nrows();
+
+        let mut result = DMatrix::zeros(n, n);
+        for i in 0 .. n {
+            for j in 0 .. n {
+                result[(fact.permutation()[i], fact.permutation()[j])] = mat[(i, j)];
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn test_indefinite() {
+        // zero diagonal, which needs 2x2 pivots
+        let mat_a = DMatrix::from_row_slice(4, 4, &[
+            0f64, 1f64, 2f64, 0f64,
+            1f64, 0f64, 0f64, 3f64,
+            2f64, 0f64, 1f64, 1f64,
+            0f64, 3f64, 1f64, -2f64
+        ]);
+
+        let fact = factorization(&mat_a).unwrap();
+        assert!((reconstruct(&fact) - &mat_a).norm() < 1E-12);
+
+        let eigenvalues = mat_a.clone().symmetric_eigenvalues();
+        let positive = eigenvalues.iter().filter(|&&x| x > 0.0).count();
+        assert_eq!((positive, 4 - positive, 0), fact.inertia());
+
+        let b = DVector::from_row_slice(&[1.0, -2.0, 3.0, 0.5]);
+        let x = fact.solve(&b).unwrap();
+        assert!((mat_a * x - b).norm() < 1E-12);
+    }
+
+    #[test]
+    fn test_singular() {
+        let mat_a = DMatrix::from_row_slice(2, 2, &[
+            1f64, 1f64,
+            1f64, 1f64
+        ]);
+        assert!(factorization(&mat_a).is_err());
+    }
+}
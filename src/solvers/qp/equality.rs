@@ -0,0 +1,229 @@
+use argmin::prelude::Error;
+use nalgebra::{DMatrix, DVector};
+
+use crate::functions::constraints::LinearConstraints;
+use crate::functions::quadratic::Quadratic;
+use crate::solvers::qp::bunchkaufman;
+
+/// Below this value the constraints are considered linearly dependent.
+static RANK_TOLERANCE : f64 = 1E-10;
+
+/// Solution x of a quadratic program, with the Lagrange multipliers λ
+/// of its constraints, such that Qx + b + Aᵀλ = 0.
+#[derive(Clone, Debug)]
+pub struct QpSolution {
+    pub param: DVector<f64>,
+    pub cost: f64,
+    pub multipliers: DVector<f64>,
+}
+
+/// How to solve the KKT system
+///
+///  [Q  Aᵀ] [x]   [-b]
+///  [A  0 ] [λ] = [ d].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KktMethod {
+    /// With a basis Z of the null space of A from the QR factorization of Aᵀ,
+    /// minimize the reduced problem in the variables of Z.
+    /// Needs only ZᵀQZ positive definite.
+    NullSpace,
+    /// Eliminate x and solve the Schur complement system
+    /// (A Q⁻¹ Aᵀ) λ = -(d + A Q⁻¹ b). Needs Q positive definite.
+    RangeSpace,
+    /// Factorize the full KKT matrix with the Bunch-Kaufman factorization,
+    /// and check its inertia.
+    SymmetricIndefinite,
+}
+
+/// Minimize ½xᵀQx + bᵀx + c subject to Ax = d, where A has full row rank.
+/// The solution is the unique minimum when the reduced Hessian ZᵀQZ is
+/// positive definite, which is checked by every method.
+///
+/// Reference:
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization. [chapter 16.1-16.2]
+pub fn solve_equality(
+    quadratic: &Quadratic,
+    constraints: &LinearConstraints,
+    method: KktMethod
+) -> Result<QpSolution, Error> {
+    let n = quadratic.b().len();
+    if constraints.mat().ncols() != n {
+        return Err(Error::msg("The linear constraints don't match the dimension of the quadratic function."));
+    }
+    if constraints.len() > n {
+        return Err(Error::msg("There are more equality constraints than variables."));
+    }
+
+    let (param, multipliers) = match method {
+        KktMethod::NullSpace => null_space(quadratic, constraints)?,
+        KktMethod::RangeSpace => range_space(quadratic, constraints)?,
+        KktMethod::SymmetricIndefinite => symmetric_indefinite(quadratic, constraints)?,
+    };
+    let cost = quadratic.evaluate_at(&param);
+
+    Ok(QpSolution { param, cost, multipliers })
+}
+
+fn null_space(quadratic: &Quadratic, constraints: &LinearConstraints)
+    -> Result<(DVector<f64>, DVector<f64>), Error>
+{
+    let (mat_q, b) = (quadratic.mat_q(), quadratic.b());
+    let (m, n) = constraints.mat().shape();
+
+    // Aᵀ = [Y Z] [R; 0], so that A Y = Rᵀ and A Z = 0
+    let qr = constraints.mat().transpose().qr();
+    let mat_r = qr.r();
+    if (0 .. m).any(|i| mat_r[(i, i)].abs() <= RANK_TOLERANCE) {
+        return Err(Error::msg("The equality constraints are linearly dependent."));
+    }
+    let mut mat_q_t = DMatrix::identity(n, n);
+    qr.q_tr_mul(&mut mat_q_t);
+    let mat_y = mat_q_t.rows(0, m).transpose();
+    let mat_z = mat_q_t.rows(m, n - m).transpose();
+
+    // particular solution x = Y R⁻ᵀ d
+    let y_part = mat_r.transpose().solve_lower_triangular(constraints.rhs())
+        .ok_or(Error::msg("Cannot solve the null space system."))?;
+    let mut param = &mat_y * y_part;
+
+    if n > m {
+        let reduced_hessian = mat_z.transpose() * mat_q * &mat_z;
+        let reduced_grad = mat_z.transpose() * (mat_q * &param + b);
+        let chol = reduced_hessian.cholesky()
+            .ok_or(Error::msg("The reduced Hessian is not positive definite."))?;
+        param -= &mat_z * chol.solve(&reduced_grad);
+    }
+
+    // R λ = -Yᵀ(Qx + b)
+    let multipliers = mat_r.solve_upper_triangular(&(-mat_y.transpose() * (mat_q * &param + b)))
+        .ok_or(Error::msg("Cannot solve the null space system."))?;
+
+    Ok((param, multipliers))
+}
+
+fn range_space(quadratic: &Quadratic, constraints: &LinearConstraints)
+    -> Result<(DVector<f64>, DVector<f64>), Error>
+{
+    let (mat_q, b) = (quadratic.mat_q(), quadratic.b());
+    let mat_a = constraints.mat();
+
+    let chol = mat_q.clone().cholesky()
+        .ok_or(Error::msg("The range space method needs a positive definite Hessian."))?;
+    let q_inv_b = chol.solve(b);
+    let q_inv_a_t = chol.solve(&mat_a.transpose());
+
+    let schur = mat_a * &q_inv_a_t;
+    let schur_chol = schur.cholesky()
+        .ok_or(Error::msg("The equality constraints are linearly dependent."))?;
+    let multipliers = -schur_chol.solve(&(constraints.rhs() + mat_a * &q_inv_b));
+
+    // x = -Q⁻¹(b + Aᵀλ)
+    let param = -q_inv_b - q_inv_a_t * &multipliers;
+
+    Ok((param, multipliers))
+}
+
+fn symmetric_indefinite(quadratic: &Quadratic, constraints: &LinearConstraints)
+    -> Result<(DVector<f64>, DVector<f64>), Error>
+{
+    let (mat_q, b) = (quadratic.mat_q(), quadratic.b());
+    let mat_a = constraints.mat();
+    let (m, n) = mat_a.shape();
+
+    let mut kkt = DMatrix::zeros(n + m, n + m);
+    kkt.slice_mut((0, 0), (n, n)).copy_from(mat_q);
+    kkt.slice_mut((n, 0), (m, n)).copy_from(mat_a);
+    kkt.slice_mut((0, n), (n, m)).copy_from(&mat_a.transpose());
+
+    let mut rhs = DVector::zeros(n + m);
+    rhs.rows_mut(0, n).copy_from(&(-b));
+    rhs.rows_mut(n, m).copy_from(constraints.rhs());
+
+    let fact = bunchkaufman::factorization(&kkt)
+        .map_err(|_| Error::msg("The KKT matrix is singular."))?;
+    // the KKT matrix has inertia (n, m, 0) iff ZᵀQZ is positive definite
+    if fact.inertia() != (n, m, 0) {
+        return Err(Error::msg("The reduced Hessian is not positive definite."));
+    }
+
+    let solution = fact.solve(&rhs)?;
+
+    Ok((solution.rows(0, n).clone_owned(), solution.rows(n, m).clone_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static METHODS : [KktMethod; 3] = [
+        KktMethod::NullSpace,
+        KktMethod::RangeSpace,
+        KktMethod::SymmetricIndefinite
+    ];
+
+    #[test]
+    fn test_equality() {
+        // example 16.2 of Nocedal and Wright
+        let quadratic = Quadratic::new(
+            DMatrix::from_row_slice(3, 3, &[
+                6f64, 2f64, 1f64,
+                2f64, 5f64, 2f64,
+                1f64, 2f64, 4f64
+            ]),
+            DVector::from_row_slice(&[-8.0, -3.0, -3.0]),
+            0.0,
+        );
+        let constraints = LinearConstraints::new(
+            DMatrix::from_row_slice(2, 3, &[
+                1f64, 0f64, 1f64,
+                0f64, 1f64, 1f64
+            ]),
+            DVector::from_row_slice(&[3.0, 0.0]),
+        ).unwrap();
+
+        for &method in METHODS.iter() {
+            let solution = solve_equality(&quadratic, &constraints, method).unwrap();
+
+            let expected = DVector::from_row_slice(&[2.0, -1.0, 1.0]);
+            assert!((&solution.param - expected).norm() < 1E-10);
+            // the book uses the convention Qx + b = Aᵀλ
+            let expected = DVector::from_row_slice(&[-3.0, 2.0]);
+            assert!((&solution.multipliers - expected).norm() < 1E-10);
+            assert!((solution.cost + 3.5).abs() < 1E-10);
+        }
+    }
+
+    #[test]
+    fn test_indefinite_hessian() {
+        // indefinite Q, positive definite on the null space of A
+        let quadratic = Quadratic::new(
+            DMatrix::from_row_slice(2, 2, &[
+                1f64, 0f64,
+                0f64, -1f64
+            ]),
+            DVector::from_row_slice(&[-1.0, 0.0]),
+            0.0,
+        );
+        let constraints = LinearConstraints::new(
+            DMatrix::from_row_slice(1, 2, &[0f64, 1f64]),
+            DVector::from_row_slice(&[2.0]),
+        ).unwrap();
+
+        for &method in [KktMethod::NullSpace, KktMethod::SymmetricIndefinite].iter() {
+            let solution = solve_equality(&quadratic, &constraints, method).unwrap();
+            assert!((&solution.param - DVector::from_row_slice(&[1.0, 2.0])).norm() < 1E-10);
+            assert!((&solution.multipliers - DVector::from_row_slice(&[2.0])).norm() < 1E-10);
+        }
+        assert!(solve_equality(&quadratic, &constraints, KktMethod::RangeSpace).is_err());
+
+        // unbounded below on the null space of A
+        let constraints = LinearConstraints::new(
+            DMatrix::from_row_slice(1, 2, &[1f64, 0f64]),
+            DVector::from_row_slice(&[2.0]),
+        ).unwrap();
+        for &method in METHODS.iter() {
+            assert!(solve_equality(&quadratic, &constraints, method).is_err());
+        }
+    }
+}
@@ -0,0 +1,397 @@
+use argmin::prelude::Error;
+use nalgebra::{Cholesky, DMatrix, DVector, Dynamic};
+
+use crate::functions::constraints::ConstrainedProblem;
+use crate::functions::quadratic::Quadratic;
+use crate::solvers::newton::cholesky;
+
+static TOLERANCE : f64 = 1E-8;
+static MAX_ITERS : usize = 100;
+
+/// Fraction of the step to the boundary of s >= 0, z >= 0.
+static STEP_TO_BOUNDARY : f64 = 0.995;
+
+static DELTA : f64 = 1E-4;
+static BETA : f64 = 100.0;
+
+/// State of the solver after an iteration.
+#[derive(Clone, Debug)]
+pub struct InteriorPointIteration {
+    pub cost: f64,
+    /// Complementarity sᵀz.
+    pub duality_gap: f64,
+    /// |(A_E x - d_E, A_I x + s - d_I)|
+    pub primal_residual: f64,
+    /// |Qx + b + A_Eᵀy + A_Iᵀz|
+    pub dual_residual: f64,
+    /// Centering parameter σ.
+    pub centering: f64,
+    pub step_length: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct InteriorPointReport {
+    pub param: DVector<f64>,
+    pub cost: f64,
+    pub equality_multipliers: DVector<f64>,
+    pub inequality_multipliers: DVector<f64>,
+    /// Slacks d_I - A_I x of the inequalities.
+    pub slacks: DVector<f64>,
+    pub duality_gap: f64,
+    pub primal_residual: f64,
+    pub dual_residual: f64,
+    pub history: Vec<InteriorPointIteration>,
+}
+
+/// Primal-dual iterate, with the slacks s and the multipliers y and z.
+#[derive(Clone)]
+struct Iterate {
+    x: DVector<f64>,
+    y: DVector<f64>,
+    z: DVector<f64>,
+    s: DVector<f64>,
+}
+
+/// Linearized KKT system, with the inequalities eliminated,
+/// factorized once per iteration for the predictor and the corrector.
+struct NewtonSystem<'a> {
+    mat_e: &'a DMatrix<f64>,
+    mat_i: &'a DMatrix<f64>,
+    // H = Q + A_Iᵀ S⁻¹Z A_I
+    hessian: Factorization,
+    // A_E H⁻¹ A_Eᵀ
+    schur: Option<Factorization>,
+    iterate: &'a Iterate,
+}
+
+/// Cholesky factorization, or the modified Cholesky factorization
+/// when the matrix is not numerically positive definite.
+enum Factorization {
+    Exact(Cholesky<f64, Dynamic>),
+    Modified(DMatrix<f64>, DVector<f64>),
+}
+
+/// Mehrotra's predictor-corrector interior point method for convex
+/// quadratic programs
+///
+///  min ½xᵀQx + bᵀx + c
+///  subject to A_E x = d_E, A_I x + s = d_I, s >= 0,
+///
+/// where Q is positive semidefinite and the inequalities are those
+/// of a ConstrainedProblem, [A_I x - b_I; l - x; x - u] <= 0, in this order.
+///
+/// Every iteration applies Newton's method to the perturbed KKT conditions
+///
+///  Qx + b + A_Eᵀy + A_Iᵀz = 0,
+///  A_E x = d_E, A_I x + s = d_I,
+///  s_i z_i = σμ, with s, z >= 0 and μ = sᵀz / m.
+///
+/// The predictor is the affine scaling step σ = 0, from which the centering
+/// parameter σ = (μ_aff / μ)³ follows, and the corrector adds the second order
+/// term Δs_aff Δz_aff to the complementarity.
+/// The inequalities and the slacks are eliminated, leaving a system in
+/// Q + A_Iᵀ S⁻¹Z A_I which is solved with the Cholesky factorization,
+/// and the equalities through its Schur complement.
+/// The solver terminates when the residuals and the duality gap are
+/// smaller than the tolerance, relatively to the data of the problem.
+///
+/// References:
+///
+/// Sanjay Mehrotra (1992). On the Implementation of a Primal-Dual Interior Point Method.
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization. [chapter 16.6]
+pub struct InteriorPoint {
+    tolerance: f64,
+    max_iters: usize,
+}
+
+impl InteriorPoint {
+    pub fn new() -> Self {
+        InteriorPoint {
+            tolerance: TOLERANCE,
+            max_iters: MAX_ITERS,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_iters(mut self, max_iters: usize) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    /// The starting point need not be feasible.
+    pub fn solve(&self, problem: &ConstrainedProblem<Quadratic>, param: DVector<f64>)
+        -> Result<InteriorPointReport, Error>
+    {
+        let quadratic = problem.function();
+        let (mat_q, b) = (quadratic.mat_q(), quadratic.b());
+        let n = b.len();
+        if param.len() != n {
+            return Err(Error::msg("The starting point doesn't match the dimension of the quadratic function."));
+        }
+
+        let (equalities, inequalities) = problem.linear_constraints(n)?;
+        let (mat_e, rhs_e) = (equalities.mat(), equalities.rhs());
+        let (mat_i, rhs_i) = (inequalities.mat(), inequalities.rhs());
+        let m = mat_i.nrows();
+
+        let residuals = |it: &Iterate| {
+            (
+                mat_q * &it.x + b + mat_e.transpose() * &it.y + mat_i.transpose() * &it.z,
+                mat_e * &it.x - rhs_e,
+                mat_i * &it.x + &it.s - rhs_i,
+            )
+        };
+        let gap = |it: &Iterate| it.s.dot(&it.z);
+        let scale = 1.0 + mat_q.amax().max(b.amax()).max(rhs_e.amax()).max(rhs_i.amax());
+
+        // starting point of Nocedal and Wright, (16.60)
+        let mut iterate = Iterate {
+            s: (rhs_i - mat_i * &param).map(|s| s.max(1.0)),
+            x: param,
+            y: DVector::zeros(mat_e.nrows()),
+            z: DVector::from_element(m, 1.0),
+        };
+        if m > 0 {
+            let (r_dual, r_eq, r_ineq) = residuals(&iterate);
+            let system = NewtonSystem::new(mat_q, mat_e, mat_i, &iterate)?;
+            let affine = system.solve(&r_dual, &r_eq, &r_ineq, &iterate.s.component_mul(&iterate.z))?;
+            iterate.s = (&iterate.s + &affine.s).map(|s| s.abs().max(1.0));
+            iterate.z = (&iterate.z + &affine.z).map(|z| z.abs().max(1.0));
+        }
+
+        let mut history = Vec::new();
+        for _ in 0 .. self.max_iters {
+            let (r_dual, r_eq, r_ineq) = residuals(&iterate);
+            let primal_residual = (r_eq.norm_squared() + r_ineq.norm_squared()).sqrt();
+            let dual_residual = r_dual.norm();
+            let duality_gap = gap(&iterate);
+
+            if primal_residual <= self.tolerance * scale
+                && dual_residual <= self.tolerance * scale
+                && duality_gap <= self.tolerance * scale
+            {
+                return Ok(InteriorPointReport {
+                    cost: quadratic.evaluate_at(&iterate.x),
+                    param: iterate.x,
+                    equality_multipliers: iterate.y,
+                    inequality_multipliers: iterate.z,
+                    slacks: iterate.s,
+                    duality_gap,
+                    primal_residual,
+                    dual_residual,
+                    history,
+                });
+            }
+
+            let system = NewtonSystem::new(mat_q, mat_e, mat_i, &iterate)?;
+            let complementarity = iterate.s.component_mul(&iterate.z);
+
+            // predictor
+            let affine = system.solve(&r_dual, &r_eq, &r_ineq, &complementarity)?;
+            let (mu, centering) = if m > 0 {
+                let mu = duality_gap / m as f64;
+                let step_length = iterate.max_step_length(&affine, 1.0);
+                let mu_affine = (&iterate.s + step_length * &affine.s)
+                    .dot(&(&iterate.z + step_length * &affine.z)) / m as f64;
+                (mu, (mu_affine / mu).powi(3))
+            }
+            else {
+                (0.0, 0.0)
+            };
+
+            // corrector
+            let target = complementarity
+                + affine.s.component_mul(&affine.z)
+                - DVector::from_element(m, centering * mu);
+            let step = system.solve(&r_dual, &r_eq, &r_ineq, &target)?;
+
+            let step_length = iterate.max_step_length(&step, STEP_TO_BOUNDARY);
+            iterate.x += step_length * &step.x;
+            iterate.y += step_length * &step.y;
+            iterate.z += step_length * &step.z;
+            iterate.s += step_length * &step.s;
+
+            let (r_dual, r_eq, r_ineq) = residuals(&iterate);
+            history.push(InteriorPointIteration {
+                cost: quadratic.evaluate_at(&iterate.x),
+                duality_gap: gap(&iterate),
+                primal_residual: (r_eq.norm_squared() + r_ineq.norm_squared()).sqrt(),
+                dual_residual: r_dual.norm(),
+                centering,
+                step_length,
+            });
+        }
+
+        Err(Error::msg("The interior point method reached the maximum number of iterations."))
+    }
+}
+
+impl Iterate {
+    /// Largest α <= 1 such that s + α/τ Δs >= 0 and z + α/τ Δz >= 0,
+    /// for the fraction τ of the step to the boundary.
+    fn max_step_length(&self, step: &Iterate, fraction: f64) -> f64 {
+        let mut step_length: f64 = 1.0;
+        for (value, delta) in self.s.iter().zip(step.s.iter()).chain(self.z.iter().zip(step.z.iter())) {
+            if *delta < 0.0 {
+                step_length = step_length.min(-fraction * value / delta);
+            }
+        }
+        step_length
+    }
+}
+
+impl Factorization {
+    fn new(mat: &DMatrix<f64>) -> Result<Self, Error> {
+        match mat.clone().cholesky() {
+            Some(chol) => Ok(Factorization::Exact(chol)),
+            None => {
+                let (mat_l, vec_d) = cholesky::factorization(mat, DELTA, BETA)?;
+                Ok(Factorization::Modified(mat_l, vec_d))
+            }
+        }
+    }
+
+    /// Solve M x = b.
+    fn solve(&self, b: &DVector<f64>) -> Result<DVector<f64>, Error> {
+        match self {
+            Factorization::Exact(chol) => Ok(chol.solve(b)),
+            Factorization::Modified(mat_l, vec_d) => cholesky::solve(mat_l, vec_d, b),
+        }
+    }
+}
+
+impl<'a> NewtonSystem<'a> {
+    fn new(
+        mat_q: &DMatrix<f64>,
+        mat_e: &'a DMatrix<f64>,
+        mat_i: &'a DMatrix<f64>,
+        iterate: &'a Iterate
+    ) -> Result<Self, Error> {
+        let weights = iterate.z.component_div(&iterate.s);
+        let hessian = Factorization::new(
+            &(mat_q + mat_i.transpose() * DMatrix::from_diagonal(&weights) * mat_i)
+        )?;
+
+        let schur = if mat_e.nrows() > 0 {
+            let mut h_inv_a_t = DMatrix::zeros(mat_e.ncols(), mat_e.nrows());
+            for j in 0 .. mat_e.nrows() {
+                h_inv_a_t.set_column(j, &hessian.solve(&mat_e.row(j).transpose())?);
+            }
+            Some(Factorization::new(&(mat_e * h_inv_a_t))?)
+        }
+        else {
+            None
+        };
+
+        Ok(NewtonSystem { mat_e, mat_i, hessian, schur, iterate })
+    }
+
+    /// Newton step for the residuals of the dual feasibility, of the equalities,
+    /// of the inequalities and the target of the complementarity s∘z.
+    fn solve(
+        &self,
+        r_dual: &DVector<f64>,
+        r_eq: &DVector<f64>,
+        r_ineq: &DVector<f64>,
+        r_comp: &DVector<f64>
+    ) -> Result<Iterate, Error> {
+        let (s, z) = (&self.iterate.s, &self.iterate.z);
+
+        // Δs = -r_I - A_I Δx and Δz = S⁻¹(-r_C + Z r_I + Z A_I Δx)
+        let scaled = (z.component_mul(r_ineq) - r_comp).component_div(s);
+        let rhs = -r_dual - self.mat_i.transpose() * &scaled;
+
+        // H Δx + A_Eᵀ Δy = rhs and A_E Δx = -r_E
+        let h_inv_rhs = self.hessian.solve(&rhs)?;
+        let (dx, dy) = match &self.schur {
+            Some(schur) => {
+                let dy = schur.solve(&(self.mat_e * &h_inv_rhs + r_eq))?;
+                let correction = self.hessian.solve(&(self.mat_e.transpose() * &dy))?;
+                (h_inv_rhs - correction, dy)
+            },
+            None => (h_inv_rhs, DVector::zeros(0)),
+        };
+
+        let ds = -r_ineq - self.mat_i * &dx;
+        let dz = scaled + z.component_mul(&(self.mat_i * &dx)).component_div(s);
+
+        Ok(Iterate { x: dx, y: dy, z: dz, s: ds })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::bounds::Bounds;
+    use crate::functions::constraints::LinearConstraints;
+
+    #[test]
+    fn test_interior_point() {
+        // example 16.4 of Nocedal and Wright
+        let quadratic = Quadratic::new(
+            DMatrix::from_diagonal(&DVector::from_row_slice(&[2.0, 2.0])),
+            DVector::from_row_slice(&[-2.0, -5.0]),
+            7.25,
+        );
+        let constraints = LinearConstraints::new(
+            DMatrix::from_row_slice(3, 2, &[
+                -1f64, 2f64,
+                1f64, 2f64,
+                1f64, -2f64
+            ]),
+            DVector::from_row_slice(&[2.0, 6.0, 2.0]),
+        ).unwrap();
+        let bounds = Bounds::new(DVector::zeros(2), DVector::from_element(2, f64::INFINITY)).unwrap();
+        let problem = ConstrainedProblem::new(quadratic)
+            .with_linear_inequalities(constraints)
+            .with_bounds(bounds);
+
+        let report = InteriorPoint::new().solve(&problem, DVector::from_row_slice(&[5.0, 5.0])).unwrap();
+
+        assert!((&report.param - DVector::from_row_slice(&[1.4, 1.7])).norm() < 1E-6);
+        let expected = DVector::from_row_slice(&[0.8, 0.0, 0.0, 0.0, 0.0]);
+        assert!((&report.inequality_multipliers - expected).norm() < 1E-6);
+        assert!(report.duality_gap < 1E-7);
+        assert!(!report.history.is_empty());
+        assert_eq!(report.history.last().unwrap().duality_gap, report.duality_gap);
+    }
+
+    #[test]
+    fn test_equality() {
+        // example 16.2 of Nocedal and Wright, with x >= 0 which is inactive
+        let quadratic = Quadratic::new(
+            DMatrix::from_row_slice(3, 3, &[
+                6f64, 2f64, 1f64,
+                2f64, 5f64, 2f64,
+                1f64, 2f64, 4f64
+            ]),
+            DVector::from_row_slice(&[-8.0, -3.0, -3.0]),
+            0.0,
+        );
+        let equalities = LinearConstraints::new(
+            DMatrix::from_row_slice(2, 3, &[
+                1f64, 0f64, 1f64,
+                0f64, 1f64, 1f64
+            ]),
+            DVector::from_row_slice(&[3.0, 0.0]),
+        ).unwrap();
+        let inequalities = LinearConstraints::new(
+            DMatrix::from_row_slice(1, 3, &[-1f64, 0f64, 0f64]),
+            DVector::from_row_slice(&[0.0]),
+        ).unwrap();
+        let problem = ConstrainedProblem::new(quadratic)
+            .with_linear_equalities(equalities)
+            .with_linear_inequalities(inequalities);
+
+        let report = InteriorPoint::new().solve(&problem, DVector::zeros(3)).unwrap();
+
+        assert!((&report.param - DVector::from_row_slice(&[2.0, -1.0, 1.0])).norm() < 1E-6);
+        assert!((&report.equality_multipliers - DVector::from_row_slice(&[-3.0, 2.0])).norm() < 1E-6);
+        assert!(report.primal_residual < 1E-7 && report.dual_residual < 1E-7);
+    }
+}
@@ -0,0 +1,8 @@
+mod activeset;
+pub mod bunchkaufman;
+mod equality;
+mod interiorpoint;
+
+pub use activeset::*;
+pub use equality::*;
+pub use interiorpoint::*;
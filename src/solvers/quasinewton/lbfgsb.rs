@@ -0,0 +1,470 @@
+use argmin::prelude::*;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::functions::bounds::Bounds;
+use crate::steplength::backtracking;
+
+static MEMORY : usize = 5;
+static TOLERANCE : f64 = 1E-5;
+
+/// Limited-memory BFGS for box-constrained problems.
+///
+/// Every iteration:
+/// 1. the generalized Cauchy point x_c is the first local minimizer of the
+///    quadratic model along the projected steepest descent path P(x - t ∇f(x)),
+///    found by visiting the breakpoints of the path in increasing order;
+/// 2. the model is minimized over the variables that are free at x_c,
+///    and the step is truncated to stay inside the box;
+/// 3. a backtracking line search along the segment from x to the result,
+///    which is feasible since the box is convex.
+///
+/// The model Hessian has the compact representation B = θI - W M Wᵀ,
+/// with W = [Y θS] built from the last m steps s and changes y of the gradient.
+/// Pairs violating sᵀy > ε yᵀy are skipped.
+/// The solver terminates when the projected gradient P(x - ∇f(x)) - x
+/// is smaller than the tolerance.
+///
+/// Reference:
+///
+/// Richard H. Byrd, Peihuang Lu, Jorge Nocedal and Ciyou Zhu (1995).
+/// A Limited Memory Algorithm for Bound Constrained Optimization.
+#[derive(Serialize, Deserialize)]
+pub struct LbfgsB {
+    bounds: Bounds,
+    memory: usize,
+    tolerance: f64,
+    // oldest pair first
+    s: Vec<DVector<f64>>,
+    y: Vec<DVector<f64>>,
+    theta: f64,
+}
+
+/// B = θI - W M Wᵀ
+struct CompactModel {
+    theta: f64,
+    mat_w: DMatrix<f64>,
+    mat_m: DMatrix<f64>,
+}
+
+impl LbfgsB {
+    pub fn new(bounds: Bounds) -> Self {
+        LbfgsB {
+            bounds,
+            memory: MEMORY,
+            tolerance: TOLERANCE,
+            s: Vec::new(),
+            y: Vec::new(),
+            theta: 1.0,
+        }
+    }
+
+    /// Number of pairs (s, y) kept in memory.
+    pub fn with_memory(mut self, memory: usize) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Terminate when the largest component of the projected gradient
+    /// is smaller than tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    fn reset(&mut self) {
+        self.s.clear();
+        self.y.clear();
+        self.theta = 1.0;
+    }
+
+    fn update(&mut self, s: DVector<f64>, y: DVector<f64>) {
+        let curvature = s.dot(&y);
+        if curvature <= f64::EPSILON * y.norm_squared() {
+            return;
+        }
+
+        self.theta = y.norm_squared() / curvature;
+        self.s.push(s);
+        self.y.push(y);
+        if self.s.len() > self.memory {
+            self.s.remove(0);
+            self.y.remove(0);
+        }
+    }
+
+    /// W = [Y θS] and
+    /// M = [-D Lᵀ; L θSᵀS]⁻¹, where D is the diagonal of SᵀY
+    /// and L its strictly lower triangular part.
+    fn model(&mut self, dimension: usize) -> CompactModel {
+        let k = self.s.len();
+        let theta = self.theta;
+
+        let mut mat_w = DMatrix::zeros(dimension, 2 * k);
+        let mut middle = DMatrix::zeros(2 * k, 2 * k);
+        for i in 0 .. k {
+            mat_w.set_column(i, &self.y[i]);
+            mat_w.set_column(k + i, &(theta * &self.s[i]));
+
+            for j in 0 .. k {
+                let sy = self.s[i].dot(&self.y[j]);
+                if i == j {
+                    middle[(i, j)] = -sy;
+                }
+                else if i > j {
+                    middle[(k + i, j)] = sy;
+                    middle[(j, k + i)] = sy;
+                }
+                middle[(k + i, k + j)] = theta * self.s[i].dot(&self.s[j]);
+            }
+        }
+
+        match middle.try_inverse() {
+            Some(mat_m) => CompactModel { theta, mat_w, mat_m },
+            None => {
+                // start again from the identity
+                self.reset();
+                CompactModel {
+                    theta: 1.0,
+                    mat_w: DMatrix::zeros(dimension, 0),
+                    mat_m: DMatrix::zeros(0, 0),
+                }
+            }
+        }
+    }
+
+    /// Generalized Cauchy point, together with c = Wᵀ(x_c - x)
+    /// and the variables not fixed at a bound.
+    fn cauchy_point(&self, model: &CompactModel, param: &DVector<f64>, grad: &DVector<f64>)
+        -> (DVector<f64>, DVector<f64>, Vec<bool>)
+    {
+        let n = param.len();
+        let (lower, upper) = (self.bounds.lower(), self.bounds.upper());
+        let (theta, mat_w, mat_m) = (model.theta, &model.mat_w, &model.mat_m);
+
+        let breakpoints: Vec<f64> = (0 .. n)
+            .map(|i| {
+                if grad[i] < 0.0 {
+                    (param[i] - upper[i]) / grad[i]
+                }
+                else if grad[i] > 0.0 {
+                    (param[i] - lower[i]) / grad[i]
+                }
+                else {
+                    f64::INFINITY
+                }
+            })
+            .collect();
+
+        let mut free: Vec<bool> = breakpoints.iter().map(|&t| t > 0.0).collect();
+        let mut dir = DVector::from_fn(n, |i, _| if free[i] { -grad[i] } else { 0.0 });
+        let mut order: Vec<usize> = (0 .. n)
+            .filter(|&i| free[i] && breakpoints[i].is_finite())
+            .collect();
+        order.sort_by(|&i, &j| breakpoints[i].partial_cmp(&breakpoints[j]).unwrap());
+
+        let mut cauchy = param.clone();
+        let mut p = mat_w.tr_mul(&dir);
+        let mut c = DVector::zeros(p.len());
+        // first and second derivatives of the model along the current segment
+        let mut slope = -dir.norm_squared();
+        let mut curvature = -theta * slope - p.dot(&(mat_m * &p));
+        let mut t_old = 0.0;
+
+        for b in order {
+            let t = breakpoints[b];
+            let dt = t - t_old;
+            if segment_minimizer(slope, curvature) < dt {
+                break;
+            }
+
+            cauchy[b] = if dir[b] > 0.0 { upper[b] } else { lower[b] };
+            let z = cauchy[b] - param[b];
+            let g = grad[b];
+            let w = mat_w.row(b).transpose();
+            c += dt * &p;
+
+            slope += dt * curvature + g * g + theta * g * z - g * w.dot(&(mat_m * &c));
+            curvature -= theta * g * g
+                + 2.0 * g * w.dot(&(mat_m * &p))
+                + g * g * w.dot(&(mat_m * &w));
+            p += g * w;
+            dir[b] = 0.0;
+            free[b] = false;
+            t_old = t;
+        }
+
+        // Past the last breakpoint, the model is minimized along the directions
+        // still free, towards infinite bounds. If there are none, or the model
+        // isn't convex along them, the Cauchy point is the last breakpoint.
+        let dt = if dir.iter().any(|&d| d != 0.0) && curvature > 0.0 {
+            segment_minimizer(slope, curvature)
+        }
+        else {
+            0.0
+        };
+        t_old += dt;
+        for i in 0 .. n {
+            if dir[i] != 0.0 {
+                cauchy[i] = param[i] + t_old * dir[i];
+            }
+        }
+        c += dt * p;
+
+        (cauchy, c, free)
+    }
+
+    /// Minimizes the model over the free variables starting from the
+    /// Cauchy point, truncating the step at the bounds.
+    fn subspace_minimization(
+        &self,
+        model: &CompactModel,
+        param: &DVector<f64>,
+        grad: &DVector<f64>,
+        cauchy: &DVector<f64>,
+        c: &DVector<f64>,
+        free: &[bool]
+    ) -> DVector<f64> {
+        let indices: Vec<usize> = (0 .. param.len()).filter(|&i| free[i]).collect();
+        if indices.is_empty() {
+            return cauchy.clone();
+        }
+        let (lower, upper) = (self.bounds.lower(), self.bounds.upper());
+        let (theta, mat_w, mat_m) = (model.theta, &model.mat_w, &model.mat_m);
+
+        // reduced gradient Zᵀ(∇f(x) + B(x_c - x)) and reduced Hessian ZᵀBZ
+        let model_grad = grad + theta * (cauchy - param) - mat_w * (mat_m * c);
+        let reduced_grad = DVector::from_fn(indices.len(), |k, _| model_grad[indices[k]]);
+        let reduced_w = DMatrix::from_fn(indices.len(), mat_w.ncols(), |k, j| mat_w[(indices[k], j)]);
+        let reduced_hessian = DMatrix::identity(indices.len(), indices.len()) * theta
+            - &reduced_w * mat_m * reduced_w.transpose();
+
+        let step = match reduced_hessian.cholesky() {
+            Some(chol) => -chol.solve(&reduced_grad),
+            None => -reduced_grad / theta,
+        };
+
+        let mut step_length: f64 = 1.0;
+        for (k, &i) in indices.iter().enumerate() {
+            if step[k] > 0.0 {
+                step_length = step_length.min((upper[i] - cauchy[i]) / step[k]);
+            }
+            else if step[k] < 0.0 {
+                step_length = step_length.min((lower[i] - cauchy[i]) / step[k]);
+            }
+        }
+
+        let mut next_param = cauchy.clone();
+        for (k, &i) in indices.iter().enumerate() {
+            next_param[i] += step_length * step[k];
+        }
+        next_param
+    }
+}
+
+/// Minimizer of t ↦ slope t + curvature t² / 2 over t >= 0.
+fn segment_minimizer(slope: f64, curvature: f64) -> f64 {
+    if slope >= 0.0 {
+        0.0
+    }
+    else if curvature > 0.0 {
+        -slope / curvature
+    }
+    else {
+        f64::INFINITY
+    }
+}
+
+impl<O> Solver<O> for LbfgsB
+where
+    O: ArgminOp<
+        Output = f64,
+        Float = f64,
+        Param = DVector<f64>,
+        Hessian = DMatrix<f64>
+    >
+{
+    const NAME: &'static str = "L-BFGS-B";
+
+    fn init(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<Option<ArgminIterData<O>>, Error> {
+        let param = state.get_param();
+        if param.len() != self.bounds.dimension() {
+            return Err(Error::msg("The initial point and the bounds have different dimensions."));
+        }
+
+        let param = self.bounds.project(&param);
+        let cost = op.apply(&param)?;
+        let grad = op.gradient(&param)?;
+        self.reset();
+
+        Ok(Some(ArgminIterData::new().param(param).cost(cost).grad(grad)))
+    }
+
+    fn next_iter(
+        &mut self,
+        op: &mut OpWrapper<O>,
+        state: &IterState<O>,
+    ) -> Result<ArgminIterData<O>, Error> {
+        let param = &state.param;
+        let grad = state.grad
+            .as_ref()
+            .ok_or(Error::msg("gradient unavailable"))?;
+
+        let model = self.model(param.len());
+        let (cauchy, c, free) = self.cauchy_point(&model, param, grad);
+        let target = self.subspace_minimization(&model, param, grad, &cauchy, &c, &free);
+
+        let mut descent_dir = target - param;
+        if grad.dot(&descent_dir) >= 0.0 {
+            self.reset();
+            descent_dir = self.bounds.projected_gradient(param, grad);
+        }
+
+        let step_length = backtracking::projected_search(
+            &*op,
+            &self.bounds,
+            param,
+            state.cost,
+            grad,
+            &descent_dir,
+            1.0
+        )?;
+
+        let next_param = self.bounds.project(&(param + step_length * &descent_dir));
+        let next_cost = op.apply(&next_param)?;
+        let next_grad = op.gradient(&next_param)?;
+
+        self.update(&next_param - param, &next_grad - grad);
+
+        Ok(ArgminIterData::new().param(next_param).cost(next_cost).grad(next_grad))
+    }
+
+    fn terminate(&mut self, state: &IterState<O>) -> TerminationReason
+    {
+        if let Some(grad) = state.grad.as_ref() {
+            if self.bounds.projected_gradient(&state.param, grad).amax() <= self.tolerance {
+                TerminationReason::TargetPrecisionReached
+            }
+            else {
+                TerminationReason::NotTerminated
+            }
+        }
+        else {
+            TerminationReason::Aborted
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::functions::Function;
+    use crate::functions::quadratic::Quadratic;
+    use crate::functions::rosenbrock::Rosenbrock2D;
+
+    #[test]
+    fn test_quadratic() {
+        let func = Quadratic::new(
+            DMatrix::from_row_slice(3, 3, &[
+                2f64, 1f64, 0f64,
+                1f64, 2f64, 0f64,
+                0f64, 0f64, 1f64
+            ]),
+            DVector::from_row_slice(&[0.0, 1.0, 2.0]),
+            0.0,
+        );
+        let bounds = Bounds::new(
+            DVector::from_row_slice(&[-1.0, 0.0, -1.0]),
+            DVector::from_row_slice(&[1.0, 1.0, 1.0]),
+        ).unwrap();
+        let x0 = DVector::from_row_slice(&[0.5, 0.5, 0.5]);
+
+        let res = func.solve(LbfgsB::new(bounds), x0);
+
+        assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+        let expected = DVector::from_row_slice(&[0.0, 0.0, -1.0]);
+        assert!((&res.state.param - &expected).norm() < 1E-5);
+    }
+
+    #[test]
+    fn test_nonconvex_cauchy_point() {
+        let param = DVector::zeros(2);
+        let grad = DVector::from_element(2, 1.0);
+        let expected = DVector::from_element(2, -1.0);
+
+        // B = diag(-1, 1), with both breakpoints at t = 1
+        let solver = LbfgsB::new(Bounds::new(
+            DVector::from_element(2, -1.0),
+            DVector::from_element(2, 1.0),
+        ).unwrap());
+        let model = CompactModel {
+            theta: 1.0,
+            mat_w: DMatrix::identity(2, 2),
+            mat_m: DMatrix::from_diagonal(&DVector::from_row_slice(&[2.0, 0.0])),
+        };
+        let (cauchy, c, free) = solver.cauchy_point(&model, &param, &grad);
+
+        assert_eq!(expected, cauchy);
+        assert!((&c - &expected).norm() < 1E-12);
+        assert_eq!(vec![false, false], free);
+
+        // B = diag(1, -1), with x2 unbounded
+        let solver = LbfgsB::new(Bounds::new(
+            DVector::from_row_slice(&[-1.0, f64::NEG_INFINITY]),
+            DVector::from_row_slice(&[1.0, f64::INFINITY]),
+        ).unwrap());
+        let model = CompactModel {
+            theta: 1.0,
+            mat_w: DMatrix::identity(2, 2),
+            mat_m: DMatrix::from_diagonal(&DVector::from_row_slice(&[0.0, 2.0])),
+        };
+        let (cauchy, c, free) = solver.cauchy_point(&model, &param, &grad);
+
+        assert_eq!(expected, cauchy);
+        assert!((&c - &expected).norm() < 1E-12);
+        assert_eq!(vec![false, true], free);
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let func = Rosenbrock2D::new(1.0, 100.0);
+        let x0 = DVector::from_row_slice(&[-1.2, 1.0]);
+
+        let bounds = Bounds::new(
+            DVector::from_row_slice(&[-2.0, -1.0]),
+            DVector::from_row_slice(&[0.5, 2.0]),
+        ).unwrap();
+        let res = func.clone().solve(LbfgsB::new(bounds), x0.clone());
+
+        assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+        assert!((&res.state.param - DVector::from_row_slice(&[0.5, 0.25])).norm() < 1E-5);
+
+        // no active bounds: plain L-BFGS
+        let unbounded = Bounds::new(
+            DVector::from_element(2, f64::NEG_INFINITY),
+            DVector::from_element(2, f64::INFINITY),
+        ).unwrap();
+        let res = func.solve(LbfgsB::new(unbounded).with_memory(3), x0);
+
+        assert_eq!(TerminationReason::TargetPrecisionReached, res.state.termination_reason);
+        assert!((&res.state.param - DVector::from_element(2, 1.0)).norm() < 1E-4);
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let bounds = Bounds::new(
+            DVector::from_row_slice(&[-2.0, -1.0]),
+            DVector::from_row_slice(&[0.5, 2.0]),
+        ).unwrap();
+        let x0 = DVector::from_row_slice(&[-1.2, 1.0, 0.0]);
+
+        assert!(Executor::new(Rosenbrock2D::new(1.0, 100.0), LbfgsB::new(bounds), x0)
+            .max_iters(10)
+            .run()
+            .is_err());
+    }
+}
@@ -1,3 +1,5 @@
 mod bfgs;
+mod lbfgsb;
 
-pub use bfgs::*;
\ No newline at end of file
+pub use bfgs::*;
+pub use lbfgsb::*;
\ No newline at end of file
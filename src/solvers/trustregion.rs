@@ -10,4 +10,24 @@ where
 
     fn subproblem(&self, state: &IterState<O>, param: &O::Param)
         -> Result<F, Error>;
+
+    /// Called with the initial point and with every trial point,
+    /// accepted or not, together with its cost.
+    fn update_model(&mut self, _op: &mut OpWrapper<O>, _param: &O::Param, _cost: F)
+        -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Gradient at an accepted point, the one of the operator by default.
+    fn model_gradient(&mut self, op: &mut OpWrapper<O>, param: &O::Param)
+        -> Result<O::Param, Error> {
+        op.gradient(param)
+    }
+
+    /// Hessian at an accepted point, the one of the operator by default.
+    /// It's always requested right after the gradient.
+    fn model_hessian(&mut self, op: &mut OpWrapper<O>, param: &O::Param)
+        -> Result<O::Hessian, Error> {
+        op.hessian(param)
+    }
 }
@@ -1,8 +1,10 @@
 use argmin::prelude::*;
 use argmin::prelude::ArgminDot;
+use nalgebra::DVector;
 use serde::{Deserialize, Serialize};
 
-use crate::steplength::{interpolation, LineFunc};
+use crate::functions::bounds::Bounds;
+use crate::steplength::{interpolation, LineFunc, ProjectedLineFunc};
 
 /// Implementation of the backtracking line search.
 /// Algorithm 3.1 of
@@ -25,11 +27,30 @@ pub fn step_length<O, F>(
         .as_ref()
         .ok_or(Error::msg("gradient unavailable"))?;
 
-    let line_cost_func = LineFunc::new(op, descent_dir, &param)?;
-    
+    search(op, &param, state.cost, gradient, descent_dir, initial_step_length)
+}
+
+/// Backtracking from an arbitrary point, given the function value and
+/// the gradient there.
+/// Useful when the function is not the one the solver is minimizing,
+/// e.g. a merit function.
+pub fn search<O, F>(
+    op: &O,
+    param: &O::Param,
+    function_value: F,
+    gradient: &O::Param,
+    descent_dir: &O::Param,
+    initial_step_length: F
+) -> Result<F, Error>
+    where
+        F: ArgminFloat,
+        O: ArgminOp<Output = F, Float = F>,
+        O::Param: ArgminScaledAdd<O::Param, F, O::Param>
+            + ArgminDot<O::Param, F>
+{
     // FIXME: avoid magic numbers.
     let linesearch = Backtracking::<F>::new::<O::Param>(
-        state.cost,
+        function_value,
         F::from_f64(0.4).unwrap(),
         F::from_f64(0.7).unwrap(),
         F::from_f64(1E-4).unwrap(),
@@ -37,6 +58,47 @@ pub fn step_length<O, F>(
         &descent_dir,
     );
 
+    run(LineFunc::new(op, descent_dir, param)?, linesearch, initial_step_length)
+}
+
+/// Backtracking from an arbitrary point, given the function value there
+/// and the directional derivative along the descent direction,
+/// or an upper bound of it for nonsmooth functions, e.g. an ℓ1 merit function.
+pub fn search_with_slope<O, F>(
+    op: &O,
+    param: &O::Param,
+    function_value: F,
+    slope: F,
+    descent_dir: &O::Param,
+    initial_step_length: F
+) -> Result<F, Error>
+    where
+        F: ArgminFloat,
+        O: ArgminOp<Output = F, Float = F>,
+        O::Param: ArgminScaledAdd<O::Param, F, O::Param>
+            + ArgminDot<O::Param, F>
+{
+    // FIXME: avoid magic numbers.
+    let linesearch = Backtracking::<F>::with_slope(
+        function_value,
+        F::from_f64(0.4).unwrap(),
+        F::from_f64(0.7).unwrap(),
+        F::from_f64(1E-4).unwrap(),
+        slope,
+    );
+
+    run(LineFunc::new(op, descent_dir, param)?, linesearch, initial_step_length)
+}
+
+fn run<L, F>(
+    line_cost_func: L,
+    linesearch: Backtracking<F>,
+    initial_step_length: F
+) -> Result<F, Error>
+    where
+        F: ArgminFloat,
+        L: ArgminOp<Param = F, Output = F, Float = F>
+{
     let res = Executor::new(line_cost_func, linesearch, initial_step_length)
     .max_iters(50)
     .run()?;
@@ -44,9 +106,39 @@ pub fn step_length<O, F>(
     Ok(res.state.param)
 }
 
+/// Backtracking along the projection arc x(α) = P(x + α d) onto the bounds,
+/// until the sufficient decrease condition
+/// f(x(α)) <= f(x) + c ∇f(x)ᵀ (x(α) - x)
+/// holds, see ProjectedLineFunc.
+///
+/// Reference:
+///
+/// Dimitri P. Bertsekas (1999). Nonlinear Programming. [chapter 2.3]
+pub fn projected_search<O>(
+    op: &O,
+    bounds: &Bounds,
+    param: &DVector<f64>,
+    function_value: f64,
+    gradient: &DVector<f64>,
+    descent_dir: &DVector<f64>,
+    initial_step_length: f64
+) -> Result<f64, Error>
+    where
+        O: ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    // FIXME: avoid magic numbers.
+    let c = 1E-4;
+    let linesearch = Backtracking::new(function_value, 0.4, 0.7, c, gradient, descent_dir);
+    let line_cost_func = ProjectedLineFunc::new(op, bounds, gradient, descent_dir, param, c);
+
+    run(line_cost_func, linesearch, initial_step_length)
+}
+
 // Given a tentative step length α
 // the next step length α' is required to be
-// in the interval 0 < ρ_min * α < α' < ρ_max * α < α
+// in the interval 0 < ρ_min * α < α' < ρ_max * α < α.
+// Trial points where the function is not finite, or cannot be
+// evaluated, are contracted by ρ_min.
 
 #[derive(Serialize, Deserialize)]
 struct Backtracking<F>
@@ -73,7 +165,18 @@ where
     where
         Param : ArgminDot<Param, F>
     {
-        let slope = gradient.dot(&descent_dir);
+        Self::with_slope(function_value, min_contraction, max_contraction, c, gradient.dot(&descent_dir))
+    }
+
+    /// Backtracking given φ'(0), or a bound on the directional derivative
+    /// when the function is not differentiable.
+    pub fn with_slope(
+        function_value: F,
+        min_contraction: F,
+        max_contraction: F,
+        c: F,
+        slope: F) -> Self
+    {
         let rho_average = (min_contraction + max_contraction) / F::from_f64(2.0).unwrap();
 
         Backtracking {
@@ -87,6 +190,18 @@ where
     }
 
 
+    /// φ(α), or +∞ when it cannot be evaluated, so that the trial step
+    /// is rejected and contracted.
+    fn value_at<O>(&self, op: &mut OpWrapper<O>, step_length: &F) -> F
+    where
+        O: ArgminOp<Float = F, Param = F, Output = F>
+    {
+        match op.apply(step_length) {
+            Ok(value) if value.is_finite() => value,
+            _ => F::infinity(),
+        }
+    }
+
     /// Quadratic interpolation as in page 58.
     fn quadratic_interpolation(&self, current_step_length: F, current_value: F)
         -> Result<F, Error>
@@ -131,7 +246,7 @@ where
         // Compute initial cost, gradient and hessian and set the initial state
 
         let initial_step_length = state.get_param();
-        let initial_value = op.apply(&initial_step_length)?;
+        let initial_value = self.value_at(op, &initial_step_length);
 
         let iter_data = ArgminIterData::<O>::new()
             .param(initial_step_length)
@@ -151,7 +266,11 @@ where
 
         let mut next_step_length = self.rho_average * current_step_length;
         
-        if state.iter == 0 {
+        if !current_value.is_finite() {
+            // rejected trial point, e.g. outside the domain of a barrier function
+            next_step_length = self.rho_min * current_step_length;
+        }
+        else if state.iter == 0 || !prev_value.is_finite() {
             if let Ok(step_length) = self.quadratic_interpolation(
                 current_step_length,
                 current_value
@@ -180,16 +299,11 @@ where
             next_step_length = max_step;
         }
 
-        let iter_data = ArgminIterData::new()
-            .param(next_step_length);
-        
-        if let Ok(fk) = op.apply(&current_step_length) {
-            Ok(iter_data.cost(fk))
-        }
-        else {
-            Ok(iter_data
-                .termination_reason(TerminationReason::Aborted))
-        }
+        let next_value = self.value_at(op, &next_step_length);
+
+        Ok(ArgminIterData::new()
+            .param(next_step_length)
+            .cost(next_value))
     }
 
     fn terminate(&mut self, state: &IterState<O>) -> TerminationReason
@@ -369,4 +483,66 @@ mod tests {
         assert_eq!(0.0, phi_first_c(next_step_length));
         assert!(phi_second_c(next_step_length) > 0.0);
     }
+
+    /// φ(α) = -α, defined only for α < 0.1
+    struct Domain {}
+
+    impl ArgminOp for Domain {
+        type Param = f64;
+        type Output = f64;
+        type Hessian = ();
+        type Jacobian = ();
+        type Float = f64;
+
+        fn apply(&self, step_length: &Self::Param) -> Result<Self::Output, Error> {
+            if *step_length < 0.1 {
+                Ok(-step_length)
+            }
+            else {
+                Ok(f64::INFINITY)
+            }
+        }
+    }
+
+    #[test]
+    fn test_infinite_values() {
+        let gradient = DVector::from_row_slice(&[-1.0]);
+        let descent_dir = DVector::from_row_slice(&[1.0]);
+        let solver = Backtracking::new(0.0, 0.4, 0.7, 1E-4, &gradient, &descent_dir);
+
+        let res = Executor::new(Domain {}, solver, 1.0)
+            .max_iters(10)
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            TerminationReason::LineSearchConditionMet,
+            res.state().termination_reason
+        );
+        // contracted by ρ_min until the trial point is in the domain
+        assert!((res.state().param - 0.064).abs() < 1E-12);
+    }
+
+    #[test]
+    fn test_projected_search() {
+        let func = Quadratic::new(DMatrix::from_diagonal_element(2, 2, 2.0), DVector::zeros(2), 0.0);
+        let x = DVector::from_row_slice(&[1.0, 1.0]);
+        let gradient = func.gradient_at(&x);
+        let value = func.evaluate_at(&x);
+
+        // the projection of the full step onto [0.5, 2]² is the minimizer
+        let bounds = Bounds::new(DVector::from_element(2, 0.5), DVector::from_element(2, 2.0)).unwrap();
+        let descent_dir = -&gradient;
+        let step_length = projected_search(&func, &bounds, &x, value, &gradient, &descent_dir, 1.0).unwrap();
+        assert_eq!(1.0, step_length);
+
+        // the full step overshoots, even once projected onto [-10, 10]²
+        let bounds = Bounds::new(DVector::from_element(2, -10.0), DVector::from_element(2, 10.0)).unwrap();
+        let descent_dir = -10.0 * &gradient;
+        let step_length = projected_search(&func, &bounds, &x, value, &gradient, &descent_dir, 1.0).unwrap();
+        assert!(step_length < 1.0);
+
+        let next_param = bounds.project(&(&x + step_length * &descent_dir));
+        assert!(func.evaluate_at(&next_param) <= value + 1E-4 * gradient.dot(&(next_param - &x)));
+    }
 }
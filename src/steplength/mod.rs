@@ -1,4 +1,7 @@
 use argmin::prelude::*;
+use nalgebra::DVector;
+
+use crate::functions::bounds::Bounds;
 
 mod interpolation;
 pub mod backtracking;
@@ -52,3 +55,59 @@ where
         Ok(grad.dot(&self.descent_dir))
     }
 }
+
+/// φ(α) = f(x(α)) - c ∇f(x)ᵀ (x(α) - x - α d) along the projection arc
+/// x(α) = P(x + α d) onto the bounds, so that the sufficient decrease
+/// condition φ(α) <= f(x) + c α ∇f(x)ᵀ d of the backtracking line search
+/// is the one along the arc, f(x(α)) <= f(x) + c ∇f(x)ᵀ (x(α) - x).
+/// Until the arc reaches a bound, φ(α) = f(x + α d).
+#[derive(Clone)]
+pub struct ProjectedLineFunc<'a, O>
+{
+    func: &'a O,
+    bounds: &'a Bounds,
+    gradient: &'a DVector<f64>,
+    descent_dir: &'a DVector<f64>,
+    x: &'a DVector<f64>,
+    c: f64,
+}
+
+impl<'a, O> ProjectedLineFunc<'a, O>
+{
+    pub fn new(
+        func: &'a O,
+        bounds: &'a Bounds,
+        gradient: &'a DVector<f64>,
+        descent_dir: &'a DVector<f64>,
+        x: &'a DVector<f64>,
+        c: f64
+    ) -> Self {
+        ProjectedLineFunc {
+            func,
+            bounds,
+            gradient,
+            descent_dir,
+            x,
+            c
+        }
+    }
+}
+
+impl<'a, O> ArgminOp for ProjectedLineFunc<'a, O>
+where
+    O : ArgminOp<Param = DVector<f64>, Output = f64, Float = f64>
+{
+    type Param = f64;
+    type Output = f64;
+    type Hessian = ();
+    type Jacobian = ();
+    type Float = f64;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let x_unprojected = self.x + *param * self.descent_dir;
+        let x_next = self.bounds.project(&x_unprojected);
+
+        let value = self.func.apply(&x_next)?;
+        Ok(value - self.c * self.gradient.dot(&(x_next - x_unprojected)))
+    }
+}
@@ -23,12 +23,16 @@ pub fn impl_solver(ast: &syn::DeriveInput) -> TokenStream {
                 op: &mut OpWrapper<O>,
                 state: &IterState<O>,
             ) -> Result<Option<ArgminIterData<O>>, Error> {
-                // Compute initial cost, gradient and hessian and set the initial state
+                // Compute initial cost, gradient and hessian and set the initial state,
+                // starting from the radius the solver was built with
         
+                self.delta = self.delta.min(MAX_DELTA);
+
                 let param = state.get_param();
                 let initial_cost = op.apply(&param)?;
-                let initial_grad = op.gradient(&param)?;
-                let initial_hessian = op.hessian(&param)?;
+                self.update_model(op, &param, initial_cost)?;
+                let initial_grad = self.model_gradient(op, &param)?;
+                let initial_hessian = self.model_hessian(op, &param)?;
         
                 let iter_data = ArgminIterData::<O>::new()
                     .param(param)
@@ -36,8 +40,6 @@ pub fn impl_solver(ast: &syn::DeriveInput) -> TokenStream {
                     .grad(initial_grad)
                     .hessian(initial_hessian);
 
-                self.delta = 0.5 * MAX_DELTA;
-
                 Ok(Some(iter_data))
             }
         
@@ -52,7 +54,19 @@ pub fn impl_solver(ast: &syn::DeriveInput) -> TokenStream {
                 let mut next_cost = op.apply(&next_param)?;
 
                 let subproblem_cost = self.subproblem(state, &descent_dir)?;
-                let rho = (state.cost - next_cost) / (state.cost - subproblem_cost);
+                let mut rho = (state.cost - next_cost) / (state.cost - subproblem_cost);
+
+                // when both the predicted and the actual reductions are at the
+                // rounding error of the cost, their ratio is meaningless:
+                // accept the step rather than shrinking the radius to zero.
+                // A tiny predicted reduction alone says nothing about a large
+                // step, e.g. with a flat model, so the cost must not increase.
+                let rounding = 16.0 * f64::EPSILON * state.cost.abs().max(1.0);
+                if (state.cost - subproblem_cost).abs() <= rounding
+                    && next_cost <= state.cost + rounding
+                {
+                    rho = 1.0;
+                }
 
                 // update the trust region radius
                 if (rho < 0.25)
@@ -61,12 +75,14 @@ pub fn impl_solver(ast: &syn::DeriveInput) -> TokenStream {
                 }
                 else
                 {
-                    if (rho > 0.75 && (rho.norm() - self.delta).abs() < 1E-5)
+                    if (rho > 0.75 && (descent_dir.norm() - self.delta).abs() < 1E-5)
                     {
                         self.delta = MAX_DELTA.min(2.0 * self.delta);
                     }
                 }
 
+                self.update_model(op, &next_param, next_cost)?;
+
                 let mut next_gradient;
                 let mut next_hessian;
                 if (rho <= ETA)
@@ -78,8 +94,8 @@ pub fn impl_solver(ast: &syn::DeriveInput) -> TokenStream {
                 }
                 else
                 {
-                    next_gradient = Some(op.gradient(&next_param)?);
-                    next_hessian = Some(op.hessian(&next_param)?);
+                    next_gradient = Some(self.model_gradient(op, &next_param)?);
+                    next_hessian = Some(self.model_hessian(op, &next_param)?);
                 }
         
                 let mut iter_data = ArgminIterData::new()